@@ -2,27 +2,60 @@ pub mod bmp_header;
 pub mod bmp_info_header;
 pub mod bmp_colour_table;
 pub mod bmp_pixel_data;
+pub mod bmp_builder;
+pub mod resize;
+pub mod pad;
+pub mod blend;
+pub mod adjust;
+pub mod convolve;
+pub mod histogram;
+pub mod threshold;
+pub mod morphology;
+pub mod draw;
+pub mod text;
+pub mod compare;
+pub mod equality;
+pub mod perceptual_hash;
+pub mod colour_profile;
+pub mod colour;
+pub mod channels;
+pub mod palette;
+pub mod palette_file;
+pub mod resolution;
+pub mod netpbm;
+pub mod tga;
 mod utils;
 
 //standard library imports
+use std::fmt;
 use std::fs::File;
-use std::io;
+use std::io::{self, Read, Seek, Write};
 
 //bmp file section imports
 use bmp_header::BmpHeader;
-use bmp_info_header::BmpInfoHeader;
+use bmp_info_header::{BmpInfoHeader, ALPHA_MASK_OFFSET, BI_ALPHABITFIELDS, BI_BITFIELDS};
 use bmp_colour_table::BmpColourTable;
 use bmp_pixel_data::BmpPixelData;
+use bmp_builder::BmpBuilder;
+use colour_profile::{ColourProfile, ProfilePosition};
 
 //import utils
-use utils::{round_up_to_multiple_of_four, rgb_to_greyscale};
+use utils::{padded_row_size, round_up_to_multiple_of_four, rgb_to_greyscale};
 
-/// A clonable struct representing a .bmp file. Top level abstraction of bitmap file. Currently only supports 24-bit .bmp files.
+/// A clonable, comparable and hashable struct representing a .bmp file. Top level abstraction of bitmap file. Supports 32-bit, 24-bit, 8-bit and 1-bit .bmp files.
 pub struct Bmp {
     pub header: BmpHeader,
     pub info_header: BmpInfoHeader,
     pub colour_table: BmpColourTable,
-    pub pixel_data: BmpPixelData
+    /// Bytes between the colour table and the pixel data, such as colour masks, kept so they are written back unchanged.
+    pub gap_data: Vec<u8>,
+    pub pixel_data: BmpPixelData,
+    /// The ICC colour profile referenced by a V5 info header, written straight after the pixel data.
+    pub colour_profile: Option<ColourProfile>,
+    /// Bytes after the pixel data, such as an embedded colour profile, kept so they are written back unchanged.
+    pub trailing_data: Vec<u8>,
+    // where the colour profile was read from, if it wasn't straight after the pixel data
+    colour_profile_position: Option<ProfilePosition>
 }
 
 impl Bmp {
@@ -51,12 +84,36 @@ impl Bmp {
             header,
             info_header,
             colour_table,
-            pixel_data
+            gap_data: Vec::new(),
+            pixel_data,
+            colour_profile: None,
+            trailing_data: Vec::new(),
+            colour_profile_position: None
         }
     }
 
+    /// Creates a `BmpBuilder` for a blank canvas with a chosen bit depth, fill colour, palette and resolution.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bumpy::bmp::Bmp;
+    ///
+    /// let bmp = Bmp::builder()
+    ///     .size(100, 100)
+    ///     .depth(8)
+    ///     .fill((255, 255, 255))
+    ///     .build();
+    /// ```
+    pub fn builder() -> BmpBuilder {
+        BmpBuilder::new()
+    }
+
     /// Builds a Bmp struct instance from a `File` object.
     ///
+    /// Every byte of the file is kept, including larger info headers, gaps before the pixel data and data after it,
+    /// so an unmodified image is written back byte-for-byte.
+    ///
     /// # Arguments
     ///
     /// * `file` - A mutable reference to a `File` object.
@@ -73,30 +130,88 @@ impl Bmp {
     /// use bumpy::bmp::Bmp;
     ///     
     /// fn main() -> io::Result<()> {
-    ///    let mut file = File::open("sample.bmp")?;
+    ///    let mut file = File::open("sample6.bmp")?;
     ///    let bmp = Bmp::build_from_file(&mut file)?;
+    ///
+    ///    // sample6.bmp has two bytes after its pixel data, which are written back too
+    ///    bmp.write_to_file("test_build_from_file")?;
+    ///    assert_eq!(std::fs::read("sample6.bmp")?, std::fs::read("test_build_from_file.bmp")?);
+    ///    std::fs::remove_file("test_build_from_file.bmp")?;
     /// 
     ///   Ok(())
     /// }
-    /// ```   
+    /// ```
+    ///
+    /// Colour profiles stay where the file put them until the image is changed:
+    ///
+    /// ```
+    /// use std::fs::File;
+    /// use std::io;
+    /// use bumpy::bmp::Bmp;
+    /// use bumpy::bmp::colour_profile::ColourProfile;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut bmp = Bmp::builder().size(4, 4).build();
+    ///     bmp.set_colour_profile(ColourProfile::srgb());
+    ///     bmp.write_to_file("test_profile_position")?;
+    ///
+    ///     // pad the profile four bytes away from the pixel data
+    ///     let mut bytes = std::fs::read("test_profile_position.bmp")?;
+    ///     let offset = u32::from_le_bytes(bytes[126..130].try_into().unwrap());
+    ///     let file_size = u32::from_le_bytes(bytes[2..6].try_into().unwrap());
+    ///
+    ///     bytes.splice(14 + offset as usize..14 + offset as usize, [0; 4]);
+    ///     bytes[126..130].copy_from_slice(&(offset + 4).to_le_bytes());
+    ///     bytes[2..6].copy_from_slice(&(file_size + 4).to_le_bytes());
+    ///     std::fs::write("test_profile_position.bmp", &bytes)?;
+    ///
+    ///     let mut file = File::open("test_profile_position.bmp")?;
+    ///     let bmp = Bmp::build_from_file(&mut file)?;
+    ///     bmp.write_to_file("test_profile_position")?;
+    ///
+    ///     assert_eq!(std::fs::read("test_profile_position.bmp")?, bytes);
+    ///     assert_eq!(bmp.colour_profile, Some(ColourProfile::srgb()));
+    ///     std::fs::remove_file("test_profile_position.bmp")?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
     pub fn build_from_file(file: &mut File) -> io::Result<Self> {
         let header = BmpHeader::build_from_file(file)?;
         let info_header = BmpInfoHeader::build_from_file(file)?;
-        let colour_table = BmpColourTable::build_from_file(file, &info_header)?;
-        let pixel_data = BmpPixelData::build_from_file(file, &header.data_offset)?;
+        let colour_table = BmpColourTable::build_from_file(file, &header.data_offset, &info_header)?;
+
+        // whatever lies between the end of the colour table and the start of the pixel data
+        let table_end = file.stream_position()?;
+        let data_offset = u64::from(u32::from_le_bytes(header.data_offset));
+        let mut gap_data = Vec::new();
+        file.take(data_offset.saturating_sub(table_end)).read_to_end(&mut gap_data)?;
+
+        let pixel_data = BmpPixelData::build_from_file(file, &header.data_offset, &info_header)?;
+
+        let mut trailing_data = Vec::new();
+        file.read_to_end(&mut trailing_data)?;
 
         let bits_per_px = u16::from_le_bytes(info_header.bits_per_px);
 
-        if bits_per_px != 24 && bits_per_px != 8{
-            return Err(io::Error::new(io::ErrorKind::InvalidData, "Only 24-bit or 8-bit .bmp files are supported"));
+        if bits_per_px != 32 && bits_per_px != 24 && bits_per_px != 8 && bits_per_px != 1 {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "Only 32-bit, 24-bit, 8-bit or 1-bit .bmp files are supported"));
         }
 
-        Ok(Bmp {
+        let mut bmp = Bmp {
             header,
             info_header,
             colour_table,
-            pixel_data
-        })
+            gap_data,
+            pixel_data,
+            colour_profile: None,
+            trailing_data,
+            colour_profile_position: None
+        };
+
+        bmp.take_colour_profile();
+
+        Ok(bmp)
     }
 
     /// Prints the contents of the `Bmp` struct to the console.
@@ -128,7 +243,7 @@ impl Bmp {
         println!("File size: {}", u32::from_le_bytes(self.header.file_size));
         println!("Reserved: {}", u32::from_le_bytes(self.header.reserved));
         println!("Data offset: {}", u32::from_le_bytes(self.header.data_offset));
-        println!("");
+        println!();
 
         println!("BMP Info Header:");
         println!("Size: {}", u32::from_le_bytes(self.info_header.size));
@@ -142,14 +257,14 @@ impl Bmp {
         println!("Y pixels per meter: {}", u32::from_le_bytes(self.info_header.y_per_m));
         println!("Colours used: {}", u32::from_le_bytes(self.info_header.colours_used));
         println!("Important colours: {}", u32::from_le_bytes(self.info_header.important_colours));
-        println!("");
+        println!();
 
         if with_color_table {
             println!("BMP Color Table:");
             for (i, color) in self.colour_table.data.iter().enumerate() {
                 println!("Color {}: {:?}", i, color);
             }
-            println!("");
+            println!();
         }
 
         if with_pixel_data {
@@ -184,14 +299,13 @@ impl Bmp {
     ///     Ok(())
     /// }
     /// ```
-
     pub fn print_all_raw (&self, with_color_table: bool, with_pixel_data: bool) {
         println!("BMP Header:");
         println!("Signature: {:?}", self.header.signature);
         println!("File size: {:?}", self.header.file_size);
         println!("Reserved: {:?}", self.header.reserved);
         println!("Data offset: {:?}", self.header.data_offset);
-        println!("");
+        println!();
 
         println!("BMP Info Header:");
         println!("Size: {:?}", self.info_header.size);
@@ -205,14 +319,14 @@ impl Bmp {
         println!("Y pixels per meter: {:?}", self.info_header.y_per_m);
         println!("Colours used: {:?}", self.info_header.colours_used);
         println!("Important colours: {:?}", self.info_header.important_colours);
-        println!("");
+        println!();
 
         if with_color_table {
             println!("BMP Color Table:");
             for (i, color) in self.colour_table.data.iter().enumerate() {
                 println!("Color {}: {:?}", i, color);
             }
-            println!("");
+            println!();
         }
 
         if with_pixel_data {
@@ -250,11 +364,55 @@ impl Bmp {
         self.header.write_to_file(&mut file)?;
         self.info_header.write_to_file(&mut file)?;
         self.colour_table.write_to_file(&mut file)?;
+        let (gap_data, profile, trailing_data) = self.colour_profile_layout();
+
+        file.write_all(&gap_data)?;
         self.pixel_data.write_to_file(&mut file)?;
+        file.write_all(&profile)?;
+        file.write_all(&trailing_data)?;
 
         Ok(())
     }
 
+    /// Checks whether the fourth byte of each 32-bit pixel is an alpha channel.
+    ///
+    /// Only 32-bit images whose header declares an alpha mask, through `BI_BITFIELDS` in a V3 or newer header or
+    /// through `BI_ALPHABITFIELDS`, have one. In plain 32-bit images the byte is reserved, and such images are read as
+    /// fully opaque.
+    ///
+    /// # Returns
+    ///
+    /// Returns `true` if the image has an alpha channel.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bumpy::bmp::Bmp;
+    ///
+    /// let bmp = Bmp::new(16, 16);
+    /// let veil = Bmp::builder().size(16, 16).depth(32).alpha(128).build();
+    ///
+    /// assert!(!bmp.has_alpha());
+    /// assert!(veil.has_alpha());
+    /// ```
+    pub fn has_alpha(&self) -> bool {
+        let compression = u32::from_le_bytes(self.info_header.compression);
+
+        if self.bits_per_px() != 32 || (compression != BI_BITFIELDS && compression != BI_ALPHABITFIELDS) {
+            return false;
+        }
+
+        // headers of 40 bytes keep their masks straight after the header, and only BI_ALPHABITFIELDS includes alpha
+        let alpha_mask = match self.info_header.read_extra(ALPHA_MASK_OFFSET) {
+            Some(mask) => mask,
+            None if compression == BI_ALPHABITFIELDS => self.gap_data.get(12..16)
+                .map_or(0, |bytes| u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])),
+            None => 0,
+        };
+
+        alpha_mask != 0
+    }
+
     /// Converts the image to greyscale.
     /// 
     /// # Examples
@@ -286,7 +444,40 @@ impl Bmp {
         }
     }
 
+    /// Converts the image to greyscale using the luminance of its colours in linear light.
+    ///
+    /// Unlike `to_greyscale`, which weights the gamma-encoded values, this matches the perceived brightness of
+    /// saturated colours. 1-bit and 8-bit images are converted through their colour table.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::fs::File;
+    /// use std::io;
+    /// use bumpy::bmp::Bmp;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut file = File::open("sample4.bmp")?;
+    ///     let mut bmp = Bmp::build_from_file(&mut file)?;
+    ///
+    ///     bmp.to_greyscale_linear();
+    ///
+    ///     bmp.write_to_file("test")?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn to_greyscale_linear(&mut self) {
+        self.map_colours(|(b, g, r)| {
+            let (r, g, b) = colour::rgb_to_linear((r, g, b));
+            let grey = colour::linear_to_srgb(0.2126 * r + 0.7152 * g + 0.0722 * b);
+
+            (grey, grey, grey)
+        });
+    }
+
     // 24 bit function definition for greyscale conversion
+    #[allow(clippy::wrong_self_convention)]
     fn to_greyscale_24(&mut self){
         let width = u32::from_le_bytes(self.info_header.width);
         let height = u32::from_le_bytes(self.info_header.height);
@@ -364,6 +555,9 @@ impl Bmp {
         self.info_header.width = height;
         self.info_header.height = width;
 
+        // the resolution turns with the image
+        std::mem::swap(&mut self.info_header.x_per_m, &mut self.info_header.y_per_m);
+
         self.pixel_data.data = new_pixel_data;
     }
 
@@ -397,6 +591,9 @@ impl Bmp {
         self.info_header.width = height;
         self.info_header.height = width;
 
+        // the resolution turns with the image
+        std::mem::swap(&mut self.info_header.x_per_m, &mut self.info_header.y_per_m);
+
         self.pixel_data.data = new_pixel_data;
     }
     
@@ -520,6 +717,303 @@ impl Bmp {
 
     }
 
+    // width of the image in pixels
+    fn width(&self) -> u32 {
+        u32::from_le_bytes(self.info_header.width)
+    }
+
+    // height of the image in pixels
+    fn height(&self) -> u32 {
+        u32::from_le_bytes(self.info_header.height)
+    }
+
+    // number of bits used to store a single pixel
+    fn bits_per_px(&self) -> u16 {
+        u16::from_le_bytes(self.info_header.bits_per_px)
+    }
+
+    // panics unless the image has a bit depth the crate can read and write
+    fn check_supported_depth(&self) {
+        let bits_per_px = self.bits_per_px();
+
+        if bits_per_px != 1 && bits_per_px != 8 && bits_per_px != 24 && bits_per_px != 32 {
+            panic!("Only 32-bit, 24-bit, 8-bit or 1-bit .bmp files are supported");
+        }
+    }
+
+    // panics unless the image stores colour table indices
+    fn check_indexed(&self) {
+        let bits_per_px = self.bits_per_px();
+
+        if bits_per_px != 1 && bits_per_px != 8 {
+            panic!("Only 8-bit or 1-bit .bmp files have a colour table");
+        }
+    }
+
+    // number of bytes in a single row of pixel data, including padding
+    fn row_size(&self) -> usize {
+        padded_row_size(self.width(), u32::from(self.bits_per_px())) as usize
+    }
+
+    // byte offset of the pixel at (x, y) in the pixel data, with y counted from the top of the image
+    fn pixel_offset(&self, x: u32, y: u32) -> usize {
+        let row = (self.height() - 1 - y) as usize;
+
+        row * self.row_size() + x as usize * usize::from(self.bits_per_px()) / 8
+    }
+
+    // looks up a colour table entry as a BGRA tuple, falling back to an evenly spaced grey for indices outside the table
+    fn palette_colour(&self, index: u8) -> (u8, u8, u8, u8) {
+        match self.colour_table.data.get(index as usize) {
+            Some(&(b, g, r, _)) => (b, g, r, 255),
+            None => {
+                let max_index = (1u32 << self.bits_per_px().min(8)) - 1;
+                let grey = (u32::from(index) * 255 / max_index) as u8;
+
+                (grey, grey, grey, 255)
+            }
+        }
+    }
+
+    // finds the colour table index for a BGRA colour, adding it to the table if there is room or falling back to the closest entry
+    fn colour_index(&mut self, colour: (u8, u8, u8, u8)) -> u8 {
+        let (b, g, r, _) = colour;
+
+        if let Some(index) = self.colour_table.find((r, g, b)) {
+            return index;
+        }
+
+        if self.colour_table.len() < 1 << self.bits_per_px().min(8) {
+            return self.colour_table.push((r, g, b));
+        }
+
+        self.colour_table.nearest((r, g, b)).unwrap_or(0)
+    }
+
+    // reads the pixel at (x, y) as a BGRA tuple
+    fn read_pixel(&self, x: u32, y: u32) -> (u8, u8, u8, u8) {
+        let offset = self.pixel_offset(x, y);
+        let data = &self.pixel_data.data;
+
+        match self.bits_per_px() {
+            1 => self.palette_colour((data[offset] >> (7 - x % 8)) & 1),
+            8 => self.palette_colour(data[offset]),
+            24 => (data[offset], data[offset + 1], data[offset + 2], 255),
+            32 => {
+                let alpha = if self.has_alpha() { data[offset + 3] } else { 255 };
+
+                (data[offset], data[offset + 1], data[offset + 2], alpha)
+            }
+            _ => panic!("Only 32-bit, 24-bit, 8-bit or 1-bit .bmp files are supported"),
+        }
+    }
+
+    // writes a BGRA colour to the pixel at (x, y), mapping it onto the colour table for 1-bit and 8-bit images
+    fn write_pixel(&mut self, x: u32, y: u32, colour: (u8, u8, u8, u8)) {
+        let offset = self.pixel_offset(x, y);
+        let (b, g, r, a) = colour;
+
+        match self.bits_per_px() {
+            1 | 8 => {
+                // existing pixels keep the grey they were shown with before the colour is added
+                self.fill_colour_table();
+
+                let table_len = self.colour_table.data.len();
+                let index = self.colour_index(colour);
+
+                if self.colour_table.data.len() != table_len {
+                    self.update_sizes();
+                }
+
+                self.write_index(x, y, index);
+            }
+            24 => self.pixel_data.data[offset..offset + 3].copy_from_slice(&[b, g, r]),
+            32 if self.has_alpha() => self.pixel_data.data[offset..offset + 4].copy_from_slice(&[b, g, r, a]),
+            32 => self.pixel_data.data[offset..offset + 3].copy_from_slice(&[b, g, r]),
+            _ => panic!("Only 32-bit, 24-bit, 8-bit or 1-bit .bmp files are supported"),
+        }
+    }
+
+    // indices without a colour table are treated as grey, so give them one that can be edited
+    fn fill_colour_table(&mut self) {
+        if self.colour_table.data.is_empty() {
+            let bits_per_px = self.bits_per_px().min(8);
+
+            self.colour_table.data = (0..=(1u16 << bits_per_px) - 1)
+                .map(|i| {
+                    let (b, g, r, _) = self.palette_colour(i as u8);
+                    (b, g, r, 0)
+                })
+                .collect();
+            self.update_sizes();
+        }
+    }
+
+    // applies `f` to every colour in the image, rewriting the colour table of 1-bit and 8-bit images instead of their pixels
+    fn map_colours<F: Fn((u8, u8, u8)) -> (u8, u8, u8)>(&mut self, f: F) {
+        let bits_per_px = self.bits_per_px();
+
+        if bits_per_px == 1 || bits_per_px == 8 {
+            self.fill_colour_table();
+
+            for entry in self.colour_table.data.iter_mut() {
+                let (b, g, r) = f((entry.0, entry.1, entry.2));
+                *entry = (b, g, r, entry.3);
+            }
+        }
+        else if bits_per_px == 24 || bits_per_px == 32 {
+            let bytes_per_px = usize::from(bits_per_px / 8);
+
+            for y in 0..self.height() {
+                for x in 0..self.width() {
+                    let offset = self.pixel_offset(x, y);
+                    let data = &mut self.pixel_data.data[offset..offset + bytes_per_px];
+                    let (b, g, r) = f((data[0], data[1], data[2]));
+
+                    data[..3].copy_from_slice(&[b, g, r]);
+                }
+            }
+        }
+        else {
+            panic!("Only 32-bit, 24-bit, 8-bit or 1-bit .bmp files are supported");
+        }
+    }
+
+    // decodes every pixel in the image as BGRA tuples, top row first
+    fn pixels(&self) -> Vec<(u8, u8, u8, u8)> {
+        let mut pixels = Vec::with_capacity((self.width() * self.height()) as usize);
+
+        for y in 0..self.height() {
+            for x in 0..self.width() {
+                pixels.push(self.read_pixel(x, y));
+            }
+        }
+
+        pixels
+    }
+
+    // reads the raw colour table index of the pixel at (x, y) in a 1-bit or 8-bit image
+    fn read_index(&self, x: u32, y: u32) -> u8 {
+        let byte = self.pixel_data.data[self.pixel_offset(x, y)];
+
+        if self.bits_per_px() == 1 {
+            (byte >> (7 - x % 8)) & 1
+        }
+        else {
+            byte
+        }
+    }
+
+    // writes a raw colour table index to the pixel at (x, y) in a 1-bit or 8-bit image
+    fn write_index(&mut self, x: u32, y: u32, index: u8) {
+        let offset = self.pixel_offset(x, y);
+
+        if self.bits_per_px() == 1 {
+            let mask = 1 << (7 - x % 8);
+            let byte = &mut self.pixel_data.data[offset];
+
+            *byte = if index & 1 == 1 { *byte | mask } else { *byte & !mask };
+        }
+        else {
+            self.pixel_data.data[offset] = index;
+        }
+    }
+
+    // reads the raw colour table indices of a 1-bit or 8-bit image, top row first
+    fn indices(&self) -> Vec<u8> {
+        let mut indices = Vec::with_capacity((self.width() * self.height()) as usize);
+
+        for y in 0..self.height() {
+            for x in 0..self.width() {
+                indices.push(self.read_index(x, y));
+            }
+        }
+
+        indices
+    }
+
+    // replaces the image with BGRA `pixels` (top row first) stored at 24 or 32 bits per pixel
+    fn set_pixels(&mut self, width: u32, height: u32, bits_per_px: u16, pixels: &[(u8, u8, u8, u8)]) {
+        let bytes_per_px = usize::from(bits_per_px / 8);
+        let row_size = padded_row_size(width, u32::from(bits_per_px)) as usize;
+
+        // the fourth byte stays reserved, and zero, unless the image keeps an alpha channel
+        let alpha = bits_per_px == 32 && self.has_alpha();
+
+        let mut data = vec![0; row_size.checked_mul(height as usize).expect("Image is too large")];
+
+        for (i, &(b, g, r, a)) in pixels.iter().enumerate() {
+            let x = i % width as usize;
+            let y = i / width as usize;
+            let offset = (height as usize - 1 - y) * row_size + x * bytes_per_px;
+
+            data[offset] = b;
+            data[offset + 1] = g;
+            data[offset + 2] = r;
+
+            if alpha {
+                data[offset + 3] = a;
+            }
+        }
+
+        self.colour_table.data.clear();
+        self.pixel_data.data = data;
+        self.set_dimensions(width, height, bits_per_px);
+    }
+
+    // replaces the image with colour table `indices` (top row first) stored at 1 or 8 bits per pixel, keeping the current colour table
+    fn set_indices(&mut self, width: u32, height: u32, bits_per_px: u16, indices: &[u8]) {
+        let row_size = padded_row_size(width, u32::from(bits_per_px)) as usize;
+
+        self.pixel_data.data = vec![0; row_size.checked_mul(height as usize).expect("Image is too large")];
+        self.set_dimensions(width, height, bits_per_px);
+
+        for (i, &index) in indices.iter().enumerate() {
+            self.write_index(i as u32 % width, i as u32 / width, index);
+        }
+    }
+
+    // updates the headers to describe pixel data of the given dimensions and bit depth
+    fn set_dimensions(&mut self, width: u32, height: u32, bits_per_px: u16) {
+        self.info_header.width = u32::to_le_bytes(width);
+        self.info_header.height = u32::to_le_bytes(height);
+
+        // 32-bit images with an alpha channel keep the bit masks that declare it
+        if !(bits_per_px == 32 && self.has_alpha()) {
+            self.info_header.compression = u32::to_le_bytes(0);
+        }
+
+        self.info_header.bits_per_px = u16::to_le_bytes(bits_per_px);
+
+        self.update_sizes();
+    }
+
+    // updates the size, offset and colour count fields of the headers to match the colour table and pixel data
+    fn update_sizes(&mut self) {
+        self.info_header.image_size = u32::to_le_bytes(self.pixel_data.data.len() as u32);
+        let colours = self.colour_table.data.len() as u32;
+        let full_table = self.bits_per_px() <= 8 && colours == 1 << self.bits_per_px();
+
+        // a count of zero already means a full table, so only replace it when the table is truncated
+        if !(full_table && u32::from_le_bytes(self.info_header.colours_used) == 0) {
+            self.info_header.colours_used = u32::to_le_bytes(colours);
+        }
+
+        if u32::from_le_bytes(self.info_header.important_colours) > self.colour_table.data.len() as u32 {
+            self.info_header.important_colours = u32::to_le_bytes(0);
+        }
+
+        let data_offset = 14 + u32::from_le_bytes(self.info_header.size) + self.colour_table.data.len() as u32 * 4
+            + self.gap_data.len() as u32;
+
+        let profile_size = self.update_colour_profile_location(data_offset);
+
+        self.header.data_offset = u32::to_le_bytes(data_offset);
+        self.header.file_size = u32::to_le_bytes(
+            data_offset + self.pixel_data.data.len() as u32 + profile_size + self.trailing_data.len() as u32
+        );
+    }
+
 }
 
 impl Clone for Bmp {
@@ -528,8 +1022,24 @@ impl Clone for Bmp {
             header: self.header.clone(),
             info_header: self.info_header.clone(),
             colour_table: self.colour_table.clone(),
-            pixel_data: self.pixel_data.clone()
+            gap_data: self.gap_data.clone(),
+            pixel_data: self.pixel_data.clone(),
+            colour_profile: self.colour_profile.clone(),
+            trailing_data: self.trailing_data.clone(),
+            colour_profile_position: self.colour_profile_position
         }
     }
 }
 
+
+impl fmt::Debug for Bmp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Bmp")
+            .field("width", &self.width())
+            .field("height", &self.height())
+            .field("bits_per_px", &self.bits_per_px())
+            .field("colours", &self.colour_table.data.len())
+            .field("pixel_data_len", &self.pixel_data.data.len())
+            .finish()
+    }
+}
@@ -0,0 +1,207 @@
+use crate::bmp::Bmp;
+use crate::bmp::utils::clamp_channel;
+
+impl Bmp {
+
+    /// Brightens or darkens the image by adding `amount` to every colour channel.
+    ///
+    /// Like the other colour adjustments, this rewrites the colour table of 1-bit and 8-bit images rather than their pixels.
+    ///
+    /// # Arguments
+    ///
+    /// * `amount` - The value to add to each channel, from -255 (black) to 255 (white).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::fs::File;
+    /// use std::io;
+    /// use bumpy::bmp::Bmp;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut file = File::open("sample.bmp")?;
+    ///     let mut bmp = Bmp::build_from_file(&mut file)?;
+    ///
+    ///     bmp.adjust_brightness(40);
+    ///
+    ///     bmp.write_to_file("test")?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn adjust_brightness(&mut self, amount: i32) {
+        self.map_channels(|c| f64::from(c) + f64::from(amount));
+    }
+
+    /// Scales the contrast of the image around mid-grey.
+    ///
+    /// # Arguments
+    ///
+    /// * `factor` - The contrast multiplier. Values below 1.0 flatten the image and values above 1.0 increase contrast.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::fs::File;
+    /// use std::io;
+    /// use bumpy::bmp::Bmp;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut file = File::open("sample.bmp")?;
+    ///     let mut bmp = Bmp::build_from_file(&mut file)?;
+    ///
+    ///     bmp.adjust_contrast(1.5);
+    ///
+    ///     bmp.write_to_file("test")?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn adjust_contrast(&mut self, factor: f64) {
+        self.map_channels(|c| (f64::from(c) - 128.0) * factor + 128.0);
+    }
+
+    /// Applies gamma correction to the image.
+    ///
+    /// # Arguments
+    ///
+    /// * `gamma` - The gamma value. Values above 1.0 brighten the mid-tones and values below 1.0 darken them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::fs::File;
+    /// use std::io;
+    /// use bumpy::bmp::Bmp;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut file = File::open("sample.bmp")?;
+    ///     let mut bmp = Bmp::build_from_file(&mut file)?;
+    ///
+    ///     bmp.adjust_gamma(2.2);
+    ///
+    ///     bmp.write_to_file("test")?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn adjust_gamma(&mut self, gamma: f64) {
+        if gamma <= 0.0 {
+            panic!("Gamma must be greater than zero");
+        }
+
+        self.map_channels(|c| 255.0 * (f64::from(c) / 255.0).powf(1.0 / gamma));
+    }
+
+    /// Scales the saturation of the image, keeping the luminance of each pixel.
+    ///
+    /// # Arguments
+    ///
+    /// * `factor` - The saturation multiplier. 0.0 produces greyscale and values above 1.0 intensify colours.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::fs::File;
+    /// use std::io;
+    /// use bumpy::bmp::Bmp;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut file = File::open("sample2.bmp")?;
+    ///     let mut bmp = Bmp::build_from_file(&mut file)?;
+    ///
+    ///     bmp.adjust_saturation(0.5);
+    ///
+    ///     bmp.write_to_file("test")?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn adjust_saturation(&mut self, factor: f64) {
+        self.map_colours(|(b, g, r)| {
+            let (b, g, r) = (f64::from(b), f64::from(g), f64::from(r));
+            let luma = 0.299 * r + 0.587 * g + 0.114 * b;
+
+            let saturate = |c: f64| clamp_channel(luma + (c - luma) * factor);
+
+            (saturate(b), saturate(g), saturate(r))
+        });
+    }
+
+    /// Rotates the hue of every colour in the image, keeping its luminance.
+    ///
+    /// # Arguments
+    ///
+    /// * `degrees` - The angle to rotate the hue by, in degrees.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::fs::File;
+    /// use std::io;
+    /// use bumpy::bmp::Bmp;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut file = File::open("sample.bmp")?;
+    ///     let mut bmp = Bmp::build_from_file(&mut file)?;
+    ///
+    ///     bmp.rotate_hue(120.0);
+    ///
+    ///     bmp.write_to_file("test")?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn rotate_hue(&mut self, degrees: f64) {
+        let (sin, cos) = degrees.to_radians().sin_cos();
+
+        // luminance preserving hue rotation matrix, as used by the SVG feColorMatrix filter
+        let matrix = [
+            [0.213 + cos * 0.787 - sin * 0.213, 0.715 - cos * 0.715 - sin * 0.715, 0.072 - cos * 0.072 + sin * 0.928],
+            [0.213 - cos * 0.213 + sin * 0.143, 0.715 + cos * 0.285 + sin * 0.140, 0.072 - cos * 0.072 - sin * 0.283],
+            [0.213 - cos * 0.213 - sin * 0.787, 0.715 - cos * 0.715 + sin * 0.715, 0.072 + cos * 0.928 + sin * 0.072],
+        ];
+
+        self.map_colours(|(b, g, r)| {
+            let rgb = [f64::from(r), f64::from(g), f64::from(b)];
+            let row = |i: usize| clamp_channel(matrix[i][0] * rgb[0] + matrix[i][1] * rgb[1] + matrix[i][2] * rgb[2]);
+
+            (row(2), row(1), row(0))
+        });
+    }
+
+    /// Inverts every colour in the image, producing a negative.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::fs::File;
+    /// use std::io;
+    /// use bumpy::bmp::Bmp;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut file = File::open("sample.bmp")?;
+    ///     let mut bmp = Bmp::build_from_file(&mut file)?;
+    ///
+    ///     bmp.invert();
+    ///
+    ///     bmp.write_to_file("test")?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn invert(&mut self) {
+        self.map_colours(|(b, g, r)| (255 - b, 255 - g, 255 - r));
+    }
+
+    // applies the same curve to every colour channel through a lookup table
+    fn map_channels<F: Fn(u8) -> f64>(&mut self, f: F) {
+        let mut table = [0; 256];
+
+        for (i, entry) in table.iter_mut().enumerate() {
+            *entry = clamp_channel(f(i as u8));
+        }
+
+        self.map_colours(|(b, g, r)| (table[b as usize], table[g as usize], table[r as usize]));
+    }
+}
@@ -0,0 +1,181 @@
+use crate::bmp::Bmp;
+use crate::bmp::colour::{linear_to_srgb, srgb_to_linear};
+use crate::bmp::utils::clamp_channel;
+
+/// How the colours of an overlaid image are combined with the image underneath.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BlendMode {
+    /// Places the overlay on top, respecting its alpha channel.
+    Normal,
+    /// Multiplies the colours, darkening the result.
+    Multiply,
+    /// Inverts, multiplies and inverts again, lightening the result.
+    Screen,
+    /// Multiplies dark areas and screens light areas of the image underneath.
+    Overlay,
+    /// Adds the colours together, clamping at white.
+    Add,
+    /// Takes the absolute difference between the colours.
+    Difference,
+}
+
+impl BlendMode {
+    // blends a backdrop channel with a source channel, both in the range 0.0..=1.0
+    fn blend(&self, backdrop: f64, source: f64) -> f64 {
+        match self {
+            BlendMode::Normal => source,
+            BlendMode::Multiply => backdrop * source,
+            BlendMode::Screen => backdrop + source - backdrop * source,
+            BlendMode::Overlay => {
+                if backdrop <= 0.5 {
+                    2.0 * backdrop * source
+                }
+                else {
+                    1.0 - 2.0 * (1.0 - backdrop) * (1.0 - source)
+                }
+            }
+            BlendMode::Add => (backdrop + source).min(1.0),
+            BlendMode::Difference => (backdrop - source).abs(),
+        }
+    }
+}
+
+impl Bmp {
+
+    /// Composites another image on top of this one with its top-left corner at (`x`, `y`).
+    ///
+    /// The overlay is clipped to the edges of this image, so it may be positioned partly or entirely outside it.
+    /// Images of different bit depths can be combined: colours are decoded before blending and stored back at this
+    /// image's bit depth. On 1-bit and 8-bit images each new colour is added to the colour table while there is room,
+    /// which grows the table and moves the pixel data, and the closest existing entry is used once it is full. The
+    /// alpha channel of 32-bit overlays controls their opacity.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The image to place on top.
+    /// * `x` - The column of this image the overlay's left edge is placed at.
+    /// * `y` - The row of this image, counted from the top, the overlay's top edge is placed at.
+    /// * `mode` - How the colours are combined.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::fs::File;
+    /// use std::io;
+    /// use bumpy::bmp::Bmp;
+    /// use bumpy::bmp::blend::BlendMode;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut file = File::open("sample.bmp")?;
+    ///     let mut bmp = Bmp::build_from_file(&mut file)?;
+    ///
+    ///     let mut file = File::open("sample4.bmp")?;
+    ///     let logo = Bmp::build_from_file(&mut file)?;
+    ///
+    ///     bmp.overlay(&logo, 400, -50, BlendMode::Screen);
+    ///
+    ///     bmp.write_to_file("test")?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn overlay(&mut self, other: &Bmp, x: i32, y: i32, mode: BlendMode) {
+        self.overlay_with(other, x, y, mode, false);
+    }
+
+    /// Composites another image on top of this one like `overlay`, but blends colours in linear light rather than on
+    /// their gamma-encoded values.
+    ///
+    /// Semi-transparent edges and blend modes then mix light the way it mixes physically, instead of coming out too
+    /// dark.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The image to place on top.
+    /// * `x` - The column of this image the overlay's left edge is placed at.
+    /// * `y` - The row of this image, counted from the top, the overlay's top edge is placed at.
+    /// * `mode` - How the colours are combined.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::fs::File;
+    /// use std::io;
+    /// use bumpy::bmp::Bmp;
+    /// use bumpy::bmp::blend::BlendMode;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut file = File::open("sample.bmp")?;
+    ///     let mut bmp = Bmp::build_from_file(&mut file)?;
+    ///
+    ///     let veil = Bmp::builder().size(256, 256).depth(32).fill((255, 255, 255)).alpha(128).build();
+    ///
+    ///     bmp.overlay_linear(&veil, 128, 128, BlendMode::Normal);
+    ///
+    ///     bmp.write_to_file("test")?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn overlay_linear(&mut self, other: &Bmp, x: i32, y: i32, mode: BlendMode) {
+        self.overlay_with(other, x, y, mode, true);
+    }
+
+    // composites another image on top of this one, blending in linear light when `linear` is set
+    fn overlay_with(&mut self, other: &Bmp, x: i32, y: i32, mode: BlendMode, linear: bool) {
+        self.check_supported_depth();
+
+        let width = i64::from(self.width());
+        let height = i64::from(self.height());
+
+        // clip the overlay to the area covered by this image
+        let start_x = i64::from(x).max(0);
+        let start_y = i64::from(y).max(0);
+        let end_x = (i64::from(x) + i64::from(other.width())).min(width);
+        let end_y = (i64::from(y) + i64::from(other.height())).min(height);
+
+        for dst_y in start_y..end_y {
+            for dst_x in start_x..end_x {
+                let source = other.read_pixel((dst_x - i64::from(x)) as u32, (dst_y - i64::from(y)) as u32);
+                let backdrop = self.read_pixel(dst_x as u32, dst_y as u32);
+
+                self.write_pixel(dst_x as u32, dst_y as u32, composite(backdrop, source, mode, linear));
+            }
+        }
+    }
+}
+
+// blends a BGRA source pixel over a BGRA backdrop pixel
+fn composite(backdrop: (u8, u8, u8, u8), source: (u8, u8, u8, u8), mode: BlendMode, linear: bool) -> (u8, u8, u8, u8) {
+    let (bb, bg, br, ba) = backdrop;
+    let (sb, sg, sr, sa) = source;
+
+    let backdrop_alpha = f64::from(ba) / 255.0;
+    let source_alpha = f64::from(sa) / 255.0;
+    let alpha = source_alpha + backdrop_alpha * (1.0 - source_alpha);
+
+    if alpha == 0.0 {
+        return (0, 0, 0, 0);
+    }
+
+    // colour channels are blended from 0.0 to 1.0, either as stored or converted to linear light
+    let decode = |value: u8| if linear { srgb_to_linear(value) } else { f64::from(value) / 255.0 };
+
+    let channel = |b: u8, s: u8| {
+        let b = decode(b);
+        let s = decode(s);
+
+        // the blended colour only applies where the backdrop is opaque
+        let mixed = (1.0 - backdrop_alpha) * s + backdrop_alpha * mode.blend(b, s);
+        let value = (source_alpha * mixed + backdrop_alpha * b * (1.0 - source_alpha)) / alpha;
+
+        if linear {
+            linear_to_srgb(value)
+        }
+        else {
+            clamp_channel(value * 255.0)
+        }
+    };
+
+    (channel(bb, sb), channel(bg, sg), channel(br, sr), (alpha * 255.0).round() as u8)
+}
@@ -0,0 +1,202 @@
+use crate::bmp::Bmp;
+use crate::bmp::bmp_header::BmpHeader;
+use crate::bmp::bmp_info_header::{
+    BmpInfoHeader, ALPHA_MASK_OFFSET, BI_BITFIELDS, BLUE_MASK_OFFSET, CS_TYPE_OFFSET, DEFAULT_PX_PER_M, GREEN_MASK_OFFSET,
+    LCS_SRGB, RED_MASK_OFFSET
+};
+use crate::bmp::bmp_colour_table::BmpColourTable;
+use crate::bmp::bmp_pixel_data::BmpPixelData;
+
+/// A builder for blank `Bmp` canvases of any supported bit depth.
+///
+/// Created with `Bmp::builder()`. Defaults to a 24-bit image filled with black.
+pub struct BmpBuilder {
+    width: u32,
+    height: u32,
+    bits_per_px: u16,
+    fill: (u8, u8, u8),
+    alpha: u8,
+    palette: Option<BmpColourTable>,
+    x_per_m: u32,
+    y_per_m: u32
+}
+
+impl BmpBuilder {
+
+    /// Creates a new `BmpBuilder` struct.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `BmpBuilder` struct.
+    pub fn new() -> Self {
+        BmpBuilder {
+            width: 0,
+            height: 0,
+            bits_per_px: 24,
+            fill: (0, 0, 0),
+            alpha: 255,
+            palette: None,
+            x_per_m: DEFAULT_PX_PER_M,
+            y_per_m: DEFAULT_PX_PER_M
+        }
+    }
+
+    /// Sets the dimensions of the image. Must be called before `build`.
+    ///
+    /// # Arguments
+    ///
+    /// * `width` - The width of the image in pixels.
+    /// * `height` - The height of the image in pixels.
+    pub fn size(mut self, width: u32, height: u32) -> Self {
+        self.width = width;
+        self.height = height;
+        self
+    }
+
+    /// Sets the bit depth of the image.
+    ///
+    /// # Arguments
+    ///
+    /// * `bits_per_px` - The number of bits per pixel: 1, 8, 24 or 32.
+    pub fn depth(mut self, bits_per_px: u16) -> Self {
+        self.bits_per_px = bits_per_px;
+        self
+    }
+
+    /// Sets the colour every pixel starts out as.
+    ///
+    /// # Arguments
+    ///
+    /// * `colour` - The fill colour as a `(red, green, blue)` tuple.
+    pub fn fill(mut self, colour: (u8, u8, u8)) -> Self {
+        self.fill = colour;
+        self
+    }
+
+    /// Sets the alpha every pixel starts out with. Only used by 32-bit images, which are built with a V4 header whose
+    /// bit masks declare an alpha channel.
+    ///
+    /// # Arguments
+    ///
+    /// * `alpha` - The opacity, from 0 (transparent) to 255 (opaque).
+    pub fn alpha(mut self, alpha: u8) -> Self {
+        self.alpha = alpha;
+        self
+    }
+
+    /// Sets the colour table of a 1-bit or 8-bit image.
+    ///
+    /// Without a palette, 1-bit images get a black and white colour table and 8-bit images start with only the fill
+    /// colour, gaining entries as other colours are drawn.
+    ///
+    /// # Arguments
+    ///
+    /// * `palette` - The colour table, with at most 2 entries for 1-bit images or 256 entries for 8-bit images.
+    pub fn palette(mut self, palette: BmpColourTable) -> Self {
+        self.palette = Some(palette);
+        self
+    }
+
+    /// Sets the physical resolution of the image. Defaults to 96 DPI.
+    ///
+    /// # Arguments
+    ///
+    /// * `x_per_m` - The horizontal resolution in pixels per metre.
+    /// * `y_per_m` - The vertical resolution in pixels per metre.
+    pub fn resolution(mut self, x_per_m: u32, y_per_m: u32) -> Self {
+        self.x_per_m = x_per_m;
+        self.y_per_m = y_per_m;
+        self
+    }
+
+    /// Builds the `Bmp` struct.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Bmp` struct with valid headers for the chosen size and bit depth.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bumpy::bmp::Bmp;
+    ///
+    /// let bmp = Bmp::builder()
+    ///     .size(64, 32)
+    ///     .depth(32)
+    ///     .fill((255, 128, 0))
+    ///     .alpha(128)
+    ///     .resolution(2835, 2835)
+    ///     .build();
+    /// ```
+    pub fn build(self) -> Bmp {
+        let (width, height, bits_per_px) = (self.width, self.height, self.bits_per_px);
+
+        if width == 0 || height == 0 {
+            panic!("Image width and height must be greater than zero");
+        }
+
+        if bits_per_px != 1 && bits_per_px != 8 && bits_per_px != 24 && bits_per_px != 32 {
+            panic!("Only 32-bit, 24-bit, 8-bit or 1-bit .bmp files are supported");
+        }
+
+        let palette = match self.palette {
+            Some(_) if bits_per_px > 8 => panic!("A palette can only be used with 1-bit or 8-bit images"),
+            Some(palette) => {
+                if palette.data.len() > 1 << bits_per_px {
+                    panic!("Palette has too many entries for the bit depth");
+                }
+
+                palette
+            }
+            None if bits_per_px == 1 => BmpColourTable { data: vec![(0, 0, 0, 0), (255, 255, 255, 0)] },
+            None => BmpColourTable::new(),
+        };
+
+        let mut info_header = BmpInfoHeader::new(width, height);
+        info_header.bits_per_px = u16::to_le_bytes(bits_per_px);
+        info_header.x_per_m = u32::to_le_bytes(self.x_per_m);
+        info_header.y_per_m = u32::to_le_bytes(self.y_per_m);
+
+        if bits_per_px == 32 {
+            // a V4 header whose bit masks declare the fourth byte of each pixel as alpha
+            info_header.size = u32::to_le_bytes(108);
+            info_header.compression = u32::to_le_bytes(BI_BITFIELDS);
+            info_header.extra = vec![0; 68];
+            info_header.write_extra(RED_MASK_OFFSET, 0x00FF_0000);
+            info_header.write_extra(GREEN_MASK_OFFSET, 0x0000_FF00);
+            info_header.write_extra(BLUE_MASK_OFFSET, 0x0000_00FF);
+            info_header.write_extra(ALPHA_MASK_OFFSET, 0xFF00_0000);
+            info_header.write_extra(CS_TYPE_OFFSET, LCS_SRGB);
+        }
+
+        let mut bmp = Bmp {
+            header: BmpHeader::new(width, height),
+            info_header,
+            colour_table: palette,
+            gap_data: Vec::new(),
+            pixel_data: BmpPixelData { data: Vec::new() },
+            colour_profile: None,
+            trailing_data: Vec::new(),
+            colour_profile_position: None
+        };
+
+        let (r, g, b) = self.fill;
+        let count = (width * height) as usize;
+
+        if bits_per_px > 8 {
+            bmp.set_pixels(width, height, bits_per_px, &vec![(b, g, r, self.alpha); count]);
+        }
+        else {
+            let index = bmp.colour_index((b, g, r, 255));
+            bmp.set_indices(width, height, bits_per_px, &vec![index; count]);
+        }
+
+        bmp
+    }
+}
+
+impl Default for BmpBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -3,6 +3,10 @@ use std::io::{self, Read, Seek, SeekFrom, Write};
 use crate::bmp::bmp_info_header::BmpInfoHeader;
 
 /// A struct representing the BMP color table.
+///
+/// Entries are stored as `(blue, green, red, reserved)` tuples, in the same order as the file. The methods below take
+/// and return `(red, green, blue)` tuples, like the rest of the crate.
+#[derive(Debug, PartialEq, Eq, Hash)]
 pub struct BmpColourTable {
     pub data: Vec<(u8, u8, u8, u8)>
 }
@@ -20,34 +24,196 @@ impl BmpColourTable {
         }
     }
 
+    /// Creates a `BmpColourTable` struct from a list of colours.
+    ///
+    /// # Arguments
+    ///
+    /// * `colours` - The entries as `(red, green, blue)` tuples, in index order.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `BmpColourTable` struct.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bumpy::bmp::bmp_colour_table::BmpColourTable;
+    ///
+    /// let palette = BmpColourTable::from_rgb(&[(0, 0, 0), (255, 0, 0), (255, 255, 255)]);
+    ///
+    /// assert_eq!(palette.get(1), Some((255, 0, 0)));
+    /// assert_eq!(palette.data[1], (0, 0, 255, 0));
+    /// ```
+    pub fn from_rgb(colours: &[(u8, u8, u8)]) -> Self {
+        BmpColourTable {
+            data: colours.iter().map(|&(r, g, b)| (b, g, r, 0)).collect()
+        }
+    }
+
+    /// Lists the colours in the table.
+    ///
+    /// # Returns
+    ///
+    /// Returns the entries as `(red, green, blue)` tuples, in index order.
+    pub fn to_rgb(&self) -> Vec<(u8, u8, u8)> {
+        self.data.iter().map(|&(b, g, r, _)| (r, g, b)).collect()
+    }
+
+    /// Returns the number of entries in the table.
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Returns `true` if the table has no entries.
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// Looks up the colour of an entry.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - The index of the entry.
+    ///
+    /// # Returns
+    ///
+    /// Returns the colour as a `(red, green, blue)` tuple, or `None` if the index is past the end of the table.
+    pub fn get(&self, index: u8) -> Option<(u8, u8, u8)> {
+        self.data.get(index as usize).map(|&(b, g, r, _)| (r, g, b))
+    }
+
+    /// Changes the colour of an existing entry.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - The index of the entry.
+    /// * `colour` - The new colour as a `(red, green, blue)` tuple.
+    pub fn set(&mut self, index: u8, colour: (u8, u8, u8)) {
+        let (r, g, b) = colour;
+
+        match self.data.get_mut(index as usize) {
+            Some(entry) => *entry = (b, g, r, entry.3),
+            None => panic!("Colour table index out of range"),
+        }
+    }
+
+    /// Appends a colour to the end of the table.
+    ///
+    /// # Arguments
+    ///
+    /// * `colour` - The colour as a `(red, green, blue)` tuple.
+    ///
+    /// # Returns
+    ///
+    /// Returns the index of the new entry.
+    pub fn push(&mut self, colour: (u8, u8, u8)) -> u8 {
+        if self.data.len() >= 256 {
+            panic!("A colour table can hold at most 256 entries");
+        }
+
+        let (r, g, b) = colour;
+        self.data.push((b, g, r, 0));
+
+        (self.data.len() - 1) as u8
+    }
+
+    /// Finds the first entry with exactly the given colour.
+    ///
+    /// # Arguments
+    ///
+    /// * `colour` - The colour as a `(red, green, blue)` tuple.
+    ///
+    /// # Returns
+    ///
+    /// Returns the index of the entry, or `None` if no entry matches.
+    pub fn find(&self, colour: (u8, u8, u8)) -> Option<u8> {
+        let (r, g, b) = colour;
+
+        self.data.iter().position(|&(tb, tg, tr, _)| (tb, tg, tr) == (b, g, r)).map(|index| index as u8)
+    }
+
+    /// Finds the entry closest to the given colour, by Euclidean distance in RGB space.
+    ///
+    /// # Arguments
+    ///
+    /// * `colour` - The colour as a `(red, green, blue)` tuple.
+    ///
+    /// # Returns
+    ///
+    /// Returns the index of the closest entry, preferring the lowest index on ties, or `None` if the table is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bumpy::bmp::bmp_colour_table::BmpColourTable;
+    ///
+    /// let palette = BmpColourTable::from_rgb(&[(0, 0, 0), (255, 0, 0), (255, 255, 255)]);
+    ///
+    /// assert_eq!(palette.find((200, 30, 20)), None);
+    /// assert_eq!(palette.nearest((200, 30, 20)), Some(1));
+    /// ```
+    pub fn nearest(&self, colour: (u8, u8, u8)) -> Option<u8> {
+        let (r, g, b) = colour;
+
+        let distance = |&(tb, tg, tr, _): &(u8, u8, u8, u8)| {
+            let db = i32::from(tb) - i32::from(b);
+            let dg = i32::from(tg) - i32::from(g);
+            let dr = i32::from(tr) - i32::from(r);
+
+            db * db + dg * dg + dr * dr
+        };
+
+        self.data.iter()
+            .enumerate()
+            .min_by_key(|(_, entry)| distance(entry))
+            .map(|(index, _)| index as u8)
+    }
+
     /// Builds a `BmpColorTable` struct from a file and the corresponding `BmpInfoHeader`.
     ///
+    /// Tables shorter than the bit depth allows are read as stored, and longer ones are cut to the bit depth. A colour
+    /// count of zero means a full table for 1-bit and 8-bit images, limited to the space before the pixel data. Tables
+    /// that don't fit before the pixel data are rejected.
+    ///
     /// # Arguments
     ///
     /// * `file` - A mutable reference to a `File` object.
+    /// * `data_offset` - A reference to the offset of the pixel data, from the `BmpHeader`.
     /// * `info_header` - A reference to the corresponding `BmpInfoHeader`.
     ///
     /// # Returns
     ///
     /// Returns a `Result` containing the `BmpColorTable` if successful, or an `io::Error` if an error occurred.
-    pub fn build_from_file(file: &mut File, info_header: &BmpInfoHeader) -> io::Result<Self> {
-        let color_table_size = u32::from_le_bytes(info_header.colours_used) * 4;
+    pub fn build_from_file(file: &mut File, data_offset: &[u8; 4], info_header: &BmpInfoHeader) -> io::Result<Self> {
+        let table_start = 14 + u32::from_le_bytes(info_header.size);
+        let bits_per_px = u16::from_le_bytes(info_header.bits_per_px);
 
-        let mut color_table = BmpColourTable {
-            data: vec![(0, 0, 0, 0); color_table_size as usize],
+        // the table has to fit between the headers and the pixel data
+        let available = u32::from_le_bytes(*data_offset).saturating_sub(table_start) / 4;
+
+        let colours_used = match u32::from_le_bytes(info_header.colours_used) {
+            0 if bits_per_px <= 8 => (1 << bits_per_px).min(available),
+            colours_used if bits_per_px <= 8 => colours_used.min(1 << bits_per_px),
+            colours_used => colours_used,
         };
 
-        // Seek to the beginning of the color table at 0036h
-        file.seek(SeekFrom::Start(54))?;
+        if colours_used > available {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "Colour table does not fit before the pixel data"));
+        }
+
+        // Seek to the beginning of the color table, straight after the info header
+        file.seek(SeekFrom::Start(u64::from(table_start)))?;
 
-        let mut buffer = vec![0; (color_table_size) as usize];
+        let mut buffer = vec![0; colours_used as usize * 4];
 
-        file.read(&mut buffer)?;
+        file.read_exact(&mut buffer)?;
 
         //group every four entries in the color table into a tuple
-        color_table.data = buffer.chunks_exact(4)
-            .map(|chunk| (chunk[0], chunk[1], chunk[2], chunk[3]))
-            .collect::<Vec<_>>();
+        let color_table = BmpColourTable {
+            data: buffer.chunks_exact(4)
+                .map(|chunk| (chunk[0], chunk[1], chunk[2], chunk[3]))
+                .collect::<Vec<_>>(),
+        };
 
         Ok(color_table)
     }
@@ -62,8 +228,8 @@ impl BmpColourTable {
     /// 
     /// Returns a `Result` containing `()` if successful, or an `io::Error` if an error occurred.
     pub fn write_to_file(&self, file: &mut File) -> io::Result<()> {
-        for (r, g, b, a) in &self.data {
-            file.write(&[*b, *g, *r, *a])?;
+        for &(b, g, r, reserved) in &self.data {
+            file.write_all(&[b, g, r, reserved])?;
         }
 
         println!("Wrote BMP color table to file");
@@ -72,6 +238,12 @@ impl BmpColourTable {
     }
 }
 
+impl Default for BmpColourTable {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Clone for BmpColourTable {
     fn clone(&self) -> Self {
         BmpColourTable {
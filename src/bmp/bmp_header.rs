@@ -1,8 +1,11 @@
+use crate::bmp::utils::round_up_to_multiple_of_four;
+
 use std::fs::File;
 use std::io::{self, Read, Write};
 
 // A struct representing the BMP file header.
 #[repr(C)]
+#[derive(Debug, PartialEq, Eq, Hash)]
 pub struct BmpHeader {
     pub signature: [u8; 2],
     pub file_size: [u8; 4],
@@ -23,7 +26,7 @@ impl BmpHeader {
     /// 
     /// Returns a `BmpHeader` struct.
     pub fn new(width: u32, height: u32) -> Self {
-        let file_size = u32::to_le_bytes((width * height * 3) + 54);
+        let file_size = u32::to_le_bytes(round_up_to_multiple_of_four(width * 3) * height + 54);
 
         BmpHeader {
             signature: [66, 77],
@@ -69,10 +72,10 @@ impl BmpHeader {
     /// 
     /// Returns a `Result` containing `()` if successful, or an `io::Error` if an error occurred.
     pub fn write_to_file(&self, file: &mut File) -> io::Result<()> {
-        file.write(&self.signature)?;
-        file.write(&self.file_size)?;
-        file.write(&self.reserved)?;
-        file.write(&self.data_offset)?;
+        file.write_all(&self.signature)?;
+        file.write_all(&self.file_size)?;
+        file.write_all(&self.reserved)?;
+        file.write_all(&self.data_offset)?;
 
         println!("Wrote BMP header to file");
 
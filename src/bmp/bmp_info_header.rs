@@ -1,8 +1,31 @@
 use std::fs::File;
 use std::io::{self, Read, Seek, SeekFrom, Write};
 
+// the resolution of new images, 96 DPI in pixels per metre
+pub(crate) const DEFAULT_PX_PER_M: u32 = 3780;
+
+// colour space types of V4 and V5 headers
+pub(crate) const LCS_SRGB: u32 = 0x7352_4742;
+pub(crate) const PROFILE_LINKED: u32 = 0x4C49_4E4B;
+pub(crate) const PROFILE_EMBEDDED: u32 = 0x4D42_4544;
+
+// compression methods that describe the channels with bit masks
+pub(crate) const BI_BITFIELDS: u32 = 3;
+pub(crate) const BI_ALPHABITFIELDS: u32 = 6;
+
+// offsets of V4 and V5 fields within `extra`
+pub(crate) const RED_MASK_OFFSET: usize = 0;
+pub(crate) const GREEN_MASK_OFFSET: usize = 4;
+pub(crate) const BLUE_MASK_OFFSET: usize = 8;
+pub(crate) const ALPHA_MASK_OFFSET: usize = 12;
+pub(crate) const CS_TYPE_OFFSET: usize = 16;
+pub(crate) const INTENT_OFFSET: usize = 68;
+pub(crate) const PROFILE_DATA_OFFSET: usize = 72;
+pub(crate) const PROFILE_SIZE_OFFSET: usize = 76;
+
 /// A struct representing the BMP info header.
 #[repr(C)]
+#[derive(Debug, PartialEq, Eq, Hash)]
 pub struct BmpInfoHeader {
     pub size: [u8; 4],
     pub width: [u8; 4],
@@ -14,7 +37,9 @@ pub struct BmpInfoHeader {
     pub x_per_m: [u8; 4],
     pub y_per_m: [u8; 4],
     pub colours_used: [u8; 4],
-    pub important_colours: [u8; 4]
+    pub important_colours: [u8; 4],
+    /// The raw fields of larger header versions, such as the colour masks and colour space of V4 and V5 headers.
+    pub extra: Vec<u8>
 }
 
 impl BmpInfoHeader {
@@ -42,10 +67,11 @@ impl BmpInfoHeader {
             bits_per_px: [24, 0],
             compression: [0, 0, 0, 0],
             image_size: [0, 0, 0, 0],
-            x_per_m: [0, 0, 0, 0],
-            y_per_m: [0, 0, 0, 0],
+            x_per_m: u32::to_le_bytes(DEFAULT_PX_PER_M),
+            y_per_m: u32::to_le_bytes(DEFAULT_PX_PER_M),
             colours_used: [0, 0, 0, 0],
-            important_colours: [0, 0, 0, 0]
+            important_colours: [0, 0, 0, 0],
+            extra: Vec::new()
         }
     }
     /// Builds a `BmpInfoHeader` struct from a file.
@@ -69,7 +95,8 @@ impl BmpInfoHeader {
             x_per_m: [0; 4],
             y_per_m: [0; 4],
             colours_used: [0; 4],
-            important_colours: [0; 4]
+            important_colours: [0; 4],
+            extra: Vec::new()
         };
 
         file.seek(SeekFrom::Start(14))?;
@@ -86,9 +113,49 @@ impl BmpInfoHeader {
         file.read_exact(&mut info_header.colours_used)?;
         file.read_exact(&mut info_header.important_colours)?;
 
+        // anything past the 40 byte core belongs to a newer header version
+        let extra_size = u32::from_le_bytes(info_header.size).saturating_sub(40);
+        info_header.extra = vec![0; extra_size as usize];
+        file.read_exact(&mut info_header.extra)?;
+
         Ok(info_header)
     }
 
+    /// Reads the colour space type of a V4 or V5 header, such as `0x73524742` ('sRGB') or `0x4D424544` ('MBED') for an
+    /// embedded ICC profile.
+    ///
+    /// # Returns
+    ///
+    /// Returns the colour space type, or `None` for headers older than V4.
+    pub fn colour_space_type(&self) -> Option<u32> {
+        self.read_extra(CS_TYPE_OFFSET)
+    }
+
+    /// Finds the ICC profile that a V5 header embeds or links to.
+    ///
+    /// # Returns
+    ///
+    /// Returns the offset of the profile from the start of the info header and its size in bytes, or `None` if the
+    /// header has no profile.
+    pub fn profile_location(&self) -> Option<(u32, u32)> {
+        match self.colour_space_type() {
+            Some(PROFILE_EMBEDDED | PROFILE_LINKED) => {
+                Some((self.read_extra(PROFILE_DATA_OFFSET)?, self.read_extra(PROFILE_SIZE_OFFSET)?))
+            }
+            _ => None,
+        }
+    }
+
+    // reads a little-endian field of a newer header version, if the header is large enough to have it
+    pub(crate) fn read_extra(&self, offset: usize) -> Option<u32> {
+        self.extra.get(offset..offset + 4).map(|bytes| u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    // writes a little-endian field of a newer header version, which the header must be large enough to have
+    pub(crate) fn write_extra(&mut self, offset: usize, value: u32) {
+        self.extra[offset..offset + 4].copy_from_slice(&value.to_le_bytes());
+    }
+
     /// Writes the `BmpInfoHeader` to a file.
     /// 
     /// # Arguments
@@ -99,17 +166,18 @@ impl BmpInfoHeader {
     /// 
     /// Returns a `Result` containing `()` if successful, or an `io::Error` if an error occurred.
     pub fn write_to_file(&self, file: &mut File) -> io::Result<()> {
-        file.write(&self.size)?;
-        file.write(&self.width)?;
-        file.write(&self.height)?;
-        file.write(&self.planes)?;
-        file.write(&self.bits_per_px)?;
-        file.write(&self.compression)?;
-        file.write(&self.image_size)?;
-        file.write(&self.x_per_m)?;
-        file.write(&self.y_per_m)?;
-        file.write(&self.colours_used)?;
-        file.write(&self.important_colours)?;
+        file.write_all(&self.size)?;
+        file.write_all(&self.width)?;
+        file.write_all(&self.height)?;
+        file.write_all(&self.planes)?;
+        file.write_all(&self.bits_per_px)?;
+        file.write_all(&self.compression)?;
+        file.write_all(&self.image_size)?;
+        file.write_all(&self.x_per_m)?;
+        file.write_all(&self.y_per_m)?;
+        file.write_all(&self.colours_used)?;
+        file.write_all(&self.important_colours)?;
+        file.write_all(&self.extra)?;
 
         println!("Wrote BMP info header to file");
 
@@ -130,7 +198,8 @@ impl Clone for BmpInfoHeader {
             x_per_m: self.x_per_m,
             y_per_m: self.y_per_m,
             colours_used: self.colours_used,
-            important_colours: self.important_colours
+            important_colours: self.important_colours,
+            extra: self.extra.clone()
         }
     }
 }
\ No newline at end of file
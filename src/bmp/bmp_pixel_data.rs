@@ -1,9 +1,11 @@
-use crate::bmp::utils::round_up_to_multiple_of_four;
+use crate::bmp::bmp_info_header::BmpInfoHeader;
+use crate::bmp::utils::{padded_row_size, round_up_to_multiple_of_four};
 
 use std::fs::File;
 use std::io::{self, Write, Read, Seek, SeekFrom};
 
 // A struct representing the BMP pixel data.
+#[derive(PartialEq, Eq, Hash)]
 pub struct BmpPixelData {
     pub data: Vec<u8>,
 }
@@ -50,22 +52,42 @@ impl BmpPixelData {
     /// # Arguments
     /// 
     /// * `file` - A mutable reference to a `File` object.
+    /// * `data_offset` - The offset of the pixel data from the start of the file.
     /// * `info_header` - A reference to the corresponding `BmpInfoHeader`.
     /// 
     /// # Returns
     /// 
     /// Returns a `Result` containing the `BmpPixelData` if successful, or an `io::Error` if an error occurred.
-    /// Only supporting 24-bit pixel data for now
-    pub fn build_from_file(file: &mut File, data_offset: &[u8; 4]) -> io::Result<Self> {
+    /// Only the pixel array is read, leaving the file cursor at the start of any data that follows it.
+    pub fn build_from_file(file: &mut File, data_offset: &[u8; 4], info_header: &BmpInfoHeader) -> io::Result<Self> {
         // Move the file cursor to the start of the pixel data
         let data_offset = u32::from_le_bytes(*data_offset) as u64;
         file.seek(SeekFrom::Start(data_offset))?;
 
+        let width = u32::from_le_bytes(info_header.width);
+        let height = i32::from_le_bytes(info_header.height).unsigned_abs();
+        let bits_per_px = u32::from(u16::from_le_bytes(info_header.bits_per_px));
+        let image_size = u32::from_le_bytes(info_header.image_size);
+
+        // uncompressed rows have a known size, while compressed data relies on the image size recorded in the header
+        let length = match u32::from_le_bytes(info_header.compression) {
+            0 | 3 | 6 => {
+                let available = file.metadata()?.len().saturating_sub(data_offset);
+
+                match padded_row_size(width, bits_per_px).checked_mul(u64::from(height)) {
+                    Some(length) if length <= available => length,
+                    _ => return Err(io::Error::new(io::ErrorKind::InvalidData, "Pixel data is larger than the file")),
+                }
+            }
+            _ if image_size > 0 => u64::from(image_size),
+            _ => u64::MAX,
+        };
+
         let mut pixel_data = BmpPixelData {
             data: Vec::new(),
         };
 
-        file.read_to_end(&mut pixel_data.data)?;
+        file.take(length).read_to_end(&mut pixel_data.data)?;
 
         Ok(pixel_data)
     }
@@ -80,7 +102,7 @@ impl BmpPixelData {
     /// 
     /// Returns a `Result` containing `()` if successful, or an `io::Error` if an error occurred.
     pub fn write_to_file(&self, file: &mut File) -> io::Result<()> {
-        file.write(&self.data)?;
+        file.write_all(&self.data)?;
 
         println!("Wrote BMP pixel data to file");
 
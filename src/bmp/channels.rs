@@ -0,0 +1,178 @@
+use crate::bmp::Bmp;
+use crate::bmp::colour::Channel;
+use crate::bmp::utils::rgb_to_greyscale;
+
+impl Bmp {
+
+    /// Splits the image into one 8-bit greyscale image per channel.
+    ///
+    /// # Returns
+    ///
+    /// Returns the red, green and blue channels, followed by the alpha channel for 32-bit images.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::fs::File;
+    /// use std::io;
+    /// use bumpy::bmp::Bmp;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut file = File::open("sample4.bmp")?;
+    ///     let bmp = Bmp::build_from_file(&mut file)?;
+    ///
+    ///     let channels = bmp.split_channels();
+    ///
+    ///     assert_eq!(channels.len(), 3);
+    ///     channels[0].write_to_file("test")?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn split_channels(&self) -> Vec<Bmp> {
+        let mut channels = vec![Channel::Red, Channel::Green, Channel::Blue];
+
+        if self.bits_per_px() == 32 {
+            channels.push(Channel::Alpha);
+        }
+
+        channels.into_iter().map(|channel| self.extract_channel(channel)).collect()
+    }
+
+    /// Merges separate channel images into a single true-colour image.
+    ///
+    /// Each channel is read from the luminance of its image, so the 8-bit greyscale images made by `split_channels`
+    /// or `extract_channel` can be merged back, as can images of any other supported bit depth.
+    ///
+    /// # Arguments
+    ///
+    /// * `red` - The image holding the red channel.
+    /// * `green` - The image holding the green channel.
+    /// * `blue` - The image holding the blue channel.
+    /// * `alpha` - The image holding the alpha channel, if any.
+    ///
+    /// # Returns
+    ///
+    /// Returns a 32-bit `Bmp` struct if an alpha channel is given, or a 24-bit one otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::fs::File;
+    /// use std::io;
+    /// use bumpy::bmp::Bmp;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut file = File::open("sample4.bmp")?;
+    ///     let bmp = Bmp::build_from_file(&mut file)?;
+    ///
+    ///     let channels = bmp.split_channels();
+    ///     let merged = Bmp::merge_channels(&channels[0], &channels[1], &channels[2], None);
+    ///
+    ///     assert!(merged == bmp);
+    ///
+    ///     // swap the red and green channels
+    ///     let swapped = Bmp::merge_channels(&channels[1], &channels[0], &channels[2], None);
+    ///     swapped.write_to_file("test")?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn merge_channels(red: &Bmp, green: &Bmp, blue: &Bmp, alpha: Option<&Bmp>) -> Bmp {
+        let width = red.width();
+        let height = red.height();
+
+        let mut sources = vec![red, green, blue];
+        sources.extend(alpha);
+
+        for source in &sources {
+            source.check_supported_depth();
+
+            if source.width() != width || source.height() != height {
+                panic!("Channel images must all have the same dimensions");
+            }
+        }
+
+        let values: Vec<Vec<u8>> = sources.iter()
+            .map(|source| source.pixels().into_iter().map(|(b, g, r, _)| rgb_to_greyscale((b, g, r)).0).collect())
+            .collect();
+
+        let pixels: Vec<(u8, u8, u8, u8)> = (0..(width * height) as usize)
+            .map(|i| (values[2][i], values[1][i], values[0][i], values.get(3).map_or(255, |alpha| alpha[i])))
+            .collect();
+
+        let bits_per_px = if alpha.is_some() { 32 } else { 24 };
+
+        let mut bmp = Bmp::builder().size(width, height).depth(bits_per_px).build();
+        bmp.set_pixels(width, height, bits_per_px, &pixels);
+        bmp.copy_resolution(red);
+
+        bmp
+    }
+
+    /// Rearranges the colour channels of the image.
+    ///
+    /// Each argument names the existing channel copied into that position, so
+    /// `swizzle(Channel::Blue, Channel::Green, Channel::Red)` swaps red and blue, fixing images whose pixels were
+    /// stored in RGB rather than BGR order. The alpha channel of 32-bit images is kept, and can be copied into a colour
+    /// channel with `Channel::Alpha`, which is fully opaque in other images. 1-bit and 8-bit images are rearranged
+    /// through their colour table.
+    ///
+    /// # Arguments
+    ///
+    /// * `red` - The channel copied into the red channel.
+    /// * `green` - The channel copied into the green channel.
+    /// * `blue` - The channel copied into the blue channel.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::fs::File;
+    /// use std::io;
+    /// use bumpy::bmp::Bmp;
+    /// use bumpy::bmp::colour::Channel;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut file = File::open("sample.bmp")?;
+    ///     let mut bmp = Bmp::build_from_file(&mut file)?;
+    ///
+    ///     bmp.swizzle(Channel::Blue, Channel::Green, Channel::Red);
+    ///
+    ///     bmp.write_to_file("test")?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn swizzle(&mut self, red: Channel, green: Channel, blue: Channel) {
+        for channel in [red, green, blue] {
+            if !matches!(channel, Channel::Red | Channel::Green | Channel::Blue | Channel::Alpha) {
+                panic!("Only the red, green, blue and alpha channels can be swizzled");
+            }
+        }
+
+        // picks a channel out of a BGRA pixel
+        let pick = |(b, g, r, a): (u8, u8, u8, u8), channel: Channel| match channel {
+            Channel::Red => r,
+            Channel::Green => g,
+            Channel::Blue => b,
+            _ => a,
+        };
+
+        let bits_per_px = self.bits_per_px();
+
+        if bits_per_px == 24 || bits_per_px == 32 {
+            let pixels: Vec<(u8, u8, u8, u8)> = self.pixels()
+                .into_iter()
+                .map(|pixel| (pick(pixel, blue), pick(pixel, green), pick(pixel, red), pixel.3))
+                .collect();
+
+            self.set_pixels(self.width(), self.height(), bits_per_px, &pixels);
+        }
+        else {
+            self.map_colours(|(b, g, r)| {
+                let pixel = (b, g, r, 255);
+                (pick(pixel, blue), pick(pixel, green), pick(pixel, red))
+            });
+        }
+    }
+}
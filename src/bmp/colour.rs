@@ -0,0 +1,448 @@
+use crate::bmp::Bmp;
+use crate::bmp::bmp_colour_table::BmpColourTable;
+use crate::bmp::utils::clamp_channel;
+
+// CIE XYZ coordinates of the D65 white point, used by sRGB
+const WHITE_X: f64 = 0.950_47;
+const WHITE_Y: f64 = 1.0;
+const WHITE_Z: f64 = 1.088_83;
+
+/// A channel of a colour model that can be extracted from an image.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Channel {
+    Red,
+    Green,
+    Blue,
+    /// Opacity, fully opaque for images without an alpha channel.
+    Alpha,
+    /// HSV and HSL hue, with 0 to 360 degrees scaled to 0 to 255.
+    Hue,
+    /// HSV saturation.
+    HsvSaturation,
+    /// HSV value, the largest of the red, green and blue channels.
+    Value,
+    /// HSL saturation.
+    HslSaturation,
+    /// HSL lightness, halfway between the largest and smallest of the red, green and blue channels.
+    Lightness,
+    /// YCbCr luma.
+    Luma,
+    /// YCbCr blue-difference chroma.
+    BlueDifference,
+    /// YCbCr red-difference chroma.
+    RedDifference,
+    /// CIE Lab lightness, with 0 to 100 scaled to 0 to 255.
+    LabLightness,
+    /// CIE Lab green to red axis, offset by 128.
+    LabA,
+    /// CIE Lab blue to yellow axis, offset by 128.
+    LabB,
+}
+
+/// Decodes a gamma-encoded sRGB channel into linear light.
+///
+/// # Arguments
+///
+/// * `value` - The sRGB channel value.
+///
+/// # Returns
+///
+/// Returns the linear intensity, from 0.0 to 1.0.
+///
+/// # Examples
+///
+/// ```
+/// use bumpy::bmp::colour::{linear_to_srgb, srgb_to_linear};
+///
+/// assert!((srgb_to_linear(128) - 0.2158).abs() < 0.0001);
+/// assert_eq!(linear_to_srgb(srgb_to_linear(128)), 128);
+/// ```
+pub fn srgb_to_linear(value: u8) -> f64 {
+    let value = f64::from(value) / 255.0;
+
+    if value <= 0.040_45 {
+        value / 12.92
+    }
+    else {
+        ((value + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+/// Encodes a linear light intensity as a gamma-encoded sRGB channel.
+///
+/// # Arguments
+///
+/// * `value` - The linear intensity, from 0.0 to 1.0. Values outside this range are clamped.
+///
+/// # Returns
+///
+/// Returns the sRGB channel value.
+pub fn linear_to_srgb(value: f64) -> u8 {
+    let value = value.clamp(0.0, 1.0);
+
+    let encoded = if value <= 0.003_130_8 {
+        value * 12.92
+    }
+    else {
+        1.055 * value.powf(1.0 / 2.4) - 0.055
+    };
+
+    (encoded * 255.0).round() as u8
+}
+
+/// Converts an sRGB colour to linear RGB.
+///
+/// # Arguments
+///
+/// * `rgb` - The colour as a `(red, green, blue)` tuple.
+///
+/// # Returns
+///
+/// Returns the linear `(red, green, blue)` intensities, each from 0.0 to 1.0.
+pub fn rgb_to_linear(rgb: (u8, u8, u8)) -> (f64, f64, f64) {
+    (srgb_to_linear(rgb.0), srgb_to_linear(rgb.1), srgb_to_linear(rgb.2))
+}
+
+/// Converts a linear RGB colour to sRGB.
+///
+/// # Arguments
+///
+/// * `linear` - The linear `(red, green, blue)` intensities, each from 0.0 to 1.0.
+///
+/// # Returns
+///
+/// Returns the colour as a `(red, green, blue)` tuple.
+pub fn linear_to_rgb(linear: (f64, f64, f64)) -> (u8, u8, u8) {
+    (linear_to_srgb(linear.0), linear_to_srgb(linear.1), linear_to_srgb(linear.2))
+}
+
+/// Converts an sRGB colour to hue, saturation and value.
+///
+/// # Arguments
+///
+/// * `rgb` - The colour as a `(red, green, blue)` tuple.
+///
+/// # Returns
+///
+/// Returns the `(hue, saturation, value)`, with hue in degrees from 0.0 to 360.0 and the others from 0.0 to 1.0.
+///
+/// # Examples
+///
+/// ```
+/// use bumpy::bmp::colour::{hsv_to_rgb, rgb_to_hsv};
+///
+/// assert_eq!(rgb_to_hsv((255, 0, 0)), (0.0, 1.0, 1.0));
+/// assert_eq!(hsv_to_rgb(rgb_to_hsv((30, 140, 200))), (30, 140, 200));
+/// ```
+pub fn rgb_to_hsv(rgb: (u8, u8, u8)) -> (f64, f64, f64) {
+    let (hue, max, min) = hue_max_min(rgb);
+    let saturation = if max == 0.0 { 0.0 } else { (max - min) / max };
+
+    (hue, saturation, max)
+}
+
+/// Converts hue, saturation and value to an sRGB colour.
+///
+/// # Arguments
+///
+/// * `hsv` - The `(hue, saturation, value)`, with hue in degrees and the others from 0.0 to 1.0.
+///
+/// # Returns
+///
+/// Returns the colour as a `(red, green, blue)` tuple.
+pub fn hsv_to_rgb(hsv: (f64, f64, f64)) -> (u8, u8, u8) {
+    let (hue, saturation, value) = hsv;
+    let chroma = value * saturation;
+
+    from_hue_chroma(hue, chroma, value - chroma)
+}
+
+/// Converts an sRGB colour to hue, saturation and lightness.
+///
+/// # Arguments
+///
+/// * `rgb` - The colour as a `(red, green, blue)` tuple.
+///
+/// # Returns
+///
+/// Returns the `(hue, saturation, lightness)`, with hue in degrees from 0.0 to 360.0 and the others from 0.0 to 1.0.
+///
+/// # Examples
+///
+/// ```
+/// use bumpy::bmp::colour::{hsl_to_rgb, rgb_to_hsl};
+///
+/// assert_eq!(rgb_to_hsl((0, 0, 255)), (240.0, 1.0, 0.5));
+/// assert_eq!(hsl_to_rgb(rgb_to_hsl((30, 140, 200))), (30, 140, 200));
+/// ```
+pub fn rgb_to_hsl(rgb: (u8, u8, u8)) -> (f64, f64, f64) {
+    let (hue, max, min) = hue_max_min(rgb);
+    let lightness = (max + min) / 2.0;
+
+    let saturation = if max == min {
+        0.0
+    }
+    else {
+        (max - min) / (1.0 - (2.0 * lightness - 1.0).abs())
+    };
+
+    (hue, saturation, lightness)
+}
+
+/// Converts hue, saturation and lightness to an sRGB colour.
+///
+/// # Arguments
+///
+/// * `hsl` - The `(hue, saturation, lightness)`, with hue in degrees and the others from 0.0 to 1.0.
+///
+/// # Returns
+///
+/// Returns the colour as a `(red, green, blue)` tuple.
+pub fn hsl_to_rgb(hsl: (f64, f64, f64)) -> (u8, u8, u8) {
+    let (hue, saturation, lightness) = hsl;
+    let chroma = (1.0 - (2.0 * lightness - 1.0).abs()) * saturation;
+
+    from_hue_chroma(hue, chroma, lightness - chroma / 2.0)
+}
+
+/// Converts an sRGB colour to full range YCbCr, as used by JPEG.
+///
+/// # Arguments
+///
+/// * `rgb` - The colour as a `(red, green, blue)` tuple.
+///
+/// # Returns
+///
+/// Returns the `(luma, blue difference, red difference)`, with the chroma channels centred on 128.
+///
+/// # Examples
+///
+/// ```
+/// use bumpy::bmp::colour::{rgb_to_ycbcr, ycbcr_to_rgb};
+///
+/// assert_eq!(rgb_to_ycbcr((255, 255, 255)), (255, 128, 128));
+/// assert_eq!(rgb_to_ycbcr((255, 0, 0)), (76, 85, 255));
+/// assert_eq!(ycbcr_to_rgb((128, 128, 128)), (128, 128, 128));
+/// ```
+pub fn rgb_to_ycbcr(rgb: (u8, u8, u8)) -> (u8, u8, u8) {
+    let (r, g, b) = (f64::from(rgb.0), f64::from(rgb.1), f64::from(rgb.2));
+
+    let y = 0.299 * r + 0.587 * g + 0.114 * b;
+    let cb = 128.0 - 0.168_736 * r - 0.331_264 * g + 0.5 * b;
+    let cr = 128.0 + 0.5 * r - 0.418_688 * g - 0.081_312 * b;
+
+    (clamp_channel(y), clamp_channel(cb), clamp_channel(cr))
+}
+
+/// Converts a full range YCbCr colour to sRGB.
+///
+/// # Arguments
+///
+/// * `ycbcr` - The `(luma, blue difference, red difference)`, with the chroma channels centred on 128.
+///
+/// # Returns
+///
+/// Returns the colour as a `(red, green, blue)` tuple.
+pub fn ycbcr_to_rgb(ycbcr: (u8, u8, u8)) -> (u8, u8, u8) {
+    let y = f64::from(ycbcr.0);
+    let cb = f64::from(ycbcr.1) - 128.0;
+    let cr = f64::from(ycbcr.2) - 128.0;
+
+    (clamp_channel(y + 1.402 * cr), clamp_channel(y - 0.344_136 * cb - 0.714_136 * cr), clamp_channel(y + 1.772 * cb))
+}
+
+/// Converts an sRGB colour to CIE Lab, relative to the D65 white point.
+///
+/// # Arguments
+///
+/// * `rgb` - The colour as a `(red, green, blue)` tuple.
+///
+/// # Returns
+///
+/// Returns the `(lightness, a, b)`, with lightness from 0.0 to 100.0 and a and b roughly from -128.0 to 127.0.
+///
+/// # Examples
+///
+/// ```
+/// use bumpy::bmp::colour::{lab_to_rgb, rgb_to_lab};
+///
+/// let (lightness, a, b) = rgb_to_lab((255, 255, 255));
+/// assert!((lightness - 100.0).abs() < 0.01 && a.abs() < 0.01 && b.abs() < 0.01);
+/// assert_eq!(lab_to_rgb(rgb_to_lab((30, 140, 200))), (30, 140, 200));
+/// ```
+pub fn rgb_to_lab(rgb: (u8, u8, u8)) -> (f64, f64, f64) {
+    let (r, g, b) = rgb_to_linear(rgb);
+
+    let x = 0.412_456_4 * r + 0.357_576_1 * g + 0.180_437_5 * b;
+    let y = 0.212_672_9 * r + 0.715_152_2 * g + 0.072_175_0 * b;
+    let z = 0.019_333_9 * r + 0.119_192_0 * g + 0.950_304_1 * b;
+
+    let f = |t: f64| {
+        if t > (6.0f64 / 29.0).powi(3) {
+            t.cbrt()
+        }
+        else {
+            t / (3.0 * (6.0f64 / 29.0).powi(2)) + 4.0 / 29.0
+        }
+    };
+
+    let (fx, fy, fz) = (f(x / WHITE_X), f(y / WHITE_Y), f(z / WHITE_Z));
+
+    (116.0 * fy - 16.0, 500.0 * (fx - fy), 200.0 * (fy - fz))
+}
+
+/// Converts a CIE Lab colour, relative to the D65 white point, to sRGB.
+///
+/// Colours outside the sRGB gamut are clamped.
+///
+/// # Arguments
+///
+/// * `lab` - The `(lightness, a, b)`, with lightness from 0.0 to 100.0.
+///
+/// # Returns
+///
+/// Returns the colour as a `(red, green, blue)` tuple.
+pub fn lab_to_rgb(lab: (f64, f64, f64)) -> (u8, u8, u8) {
+    let (lightness, a, b) = lab;
+
+    let fy = (lightness + 16.0) / 116.0;
+    let fx = fy + a / 500.0;
+    let fz = fy - b / 200.0;
+
+    let f_inverse = |t: f64| {
+        if t > 6.0 / 29.0 {
+            t.powi(3)
+        }
+        else {
+            3.0 * (6.0f64 / 29.0).powi(2) * (t - 4.0 / 29.0)
+        }
+    };
+
+    let (x, y, z) = (WHITE_X * f_inverse(fx), WHITE_Y * f_inverse(fy), WHITE_Z * f_inverse(fz));
+
+    linear_to_rgb((
+        3.240_454_2 * x - 1.537_138_5 * y - 0.498_531_4 * z,
+        -0.969_266_0 * x + 1.876_010_8 * y + 0.041_556_0 * z,
+        0.055_643_4 * x - 0.204_025_9 * y + 1.057_225_2 * z
+    ))
+}
+
+impl Bmp {
+
+    /// Extracts one channel of the image as an 8-bit greyscale image.
+    ///
+    /// Colours are decoded first, so any supported bit depth can be used. Channels with ranges other than 0 to 255
+    /// are scaled or offset to fit, as described on each `Channel` variant.
+    ///
+    /// # Arguments
+    ///
+    /// * `channel` - The channel to extract.
+    ///
+    /// # Returns
+    ///
+    /// Returns an 8-bit `Bmp` struct the same size as this image, with a greyscale colour table.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::fs::File;
+    /// use std::io;
+    /// use bumpy::bmp::Bmp;
+    /// use bumpy::bmp::colour::Channel;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut file = File::open("sample4.bmp")?;
+    ///     let bmp = Bmp::build_from_file(&mut file)?;
+    ///
+    ///     let value = bmp.extract_channel(Channel::Value);
+    ///
+    ///     value.write_to_file("test")?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn extract_channel(&self, channel: Channel) -> Bmp {
+        self.check_supported_depth();
+
+        let values: Vec<u8> = self.pixels()
+            .into_iter()
+            .map(|(b, g, r, a)| channel_value((r, g, b), a, channel))
+            .collect();
+
+        let mut bmp = greyscale_image(self.width(), self.height(), &values);
+        bmp.copy_resolution(self);
+
+        bmp
+    }
+}
+
+// the value of one channel of an sRGB colour, scaled to the range of a byte
+fn channel_value(rgb: (u8, u8, u8), alpha: u8, channel: Channel) -> u8 {
+    match channel {
+        Channel::Red => rgb.0,
+        Channel::Green => rgb.1,
+        Channel::Blue => rgb.2,
+        Channel::Alpha => alpha,
+        Channel::Hue => clamp_channel(rgb_to_hsv(rgb).0 / 360.0 * 255.0),
+        Channel::HsvSaturation => clamp_channel(rgb_to_hsv(rgb).1 * 255.0),
+        Channel::Value => clamp_channel(rgb_to_hsv(rgb).2 * 255.0),
+        Channel::HslSaturation => clamp_channel(rgb_to_hsl(rgb).1 * 255.0),
+        Channel::Lightness => clamp_channel(rgb_to_hsl(rgb).2 * 255.0),
+        Channel::Luma => rgb_to_ycbcr(rgb).0,
+        Channel::BlueDifference => rgb_to_ycbcr(rgb).1,
+        Channel::RedDifference => rgb_to_ycbcr(rgb).2,
+        Channel::LabLightness => clamp_channel(rgb_to_lab(rgb).0 / 100.0 * 255.0),
+        Channel::LabA => clamp_channel(rgb_to_lab(rgb).1 + 128.0),
+        Channel::LabB => clamp_channel(rgb_to_lab(rgb).2 + 128.0),
+    }
+}
+
+// builds an 8-bit image with a greyscale colour table from one byte per pixel, top row first
+pub(crate) fn greyscale_image(width: u32, height: u32, values: &[u8]) -> Bmp {
+    let palette = BmpColourTable { data: (0..=255).map(|grey| (grey, grey, grey, 0)).collect() };
+    let mut bmp = Bmp::builder().size(width, height).depth(8).palette(palette).build();
+
+    bmp.set_indices(width, height, 8, values);
+
+    bmp
+}
+
+// hue in degrees along with the largest and smallest channels, all from an sRGB colour
+fn hue_max_min(rgb: (u8, u8, u8)) -> (f64, f64, f64) {
+    let (r, g, b) = (f64::from(rgb.0) / 255.0, f64::from(rgb.1) / 255.0, f64::from(rgb.2) / 255.0);
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let delta = max - min;
+
+    let hue = if delta == 0.0 {
+        0.0
+    }
+    else if max == r {
+        60.0 * ((g - b) / delta).rem_euclid(6.0)
+    }
+    else if max == g {
+        60.0 * ((b - r) / delta + 2.0)
+    }
+    else {
+        60.0 * ((r - g) / delta + 4.0)
+    };
+
+    (hue, max, min)
+}
+
+// builds an sRGB colour from a hue in degrees, a chroma and the amount added to every channel, all from 0.0 to 1.0
+fn from_hue_chroma(hue: f64, chroma: f64, offset: f64) -> (u8, u8, u8) {
+    let sector = hue.rem_euclid(360.0) / 60.0;
+    let second = chroma * (1.0 - (sector % 2.0 - 1.0).abs());
+
+    let (r, g, b) = match sector as u32 {
+        0 => (chroma, second, 0.0),
+        1 => (second, chroma, 0.0),
+        2 => (0.0, chroma, second),
+        3 => (0.0, second, chroma),
+        4 => (second, 0.0, chroma),
+        _ => (chroma, 0.0, second),
+    };
+
+    (clamp_channel((r + offset) * 255.0), clamp_channel((g + offset) * 255.0), clamp_channel((b + offset) * 255.0))
+}
@@ -0,0 +1,318 @@
+use crate::bmp::Bmp;
+use crate::bmp::bmp_info_header::{
+    BI_ALPHABITFIELDS, BI_BITFIELDS, CS_TYPE_OFFSET, INTENT_OFFSET, LCS_SRGB, PROFILE_DATA_OFFSET, PROFILE_EMBEDDED,
+    PROFILE_LINKED, PROFILE_SIZE_OFFSET
+};
+
+/// An ICC colour profile attached to an image with a V5 info header.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ColourProfile {
+    /// The ICC profile data, stored in the file after the pixel data.
+    Embedded(Vec<u8>),
+    /// The path of an ICC profile file stored elsewhere.
+    Linked(String),
+}
+
+// where a profile read from a file was kept when it wasn't straight after the pixel data
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum ProfilePosition {
+    // this many bytes into the gap data
+    Gap(usize),
+    // this many bytes into the trailing data
+    Trailing(usize),
+}
+
+impl ColourProfile {
+
+    /// Creates an embedded ICC v4 profile describing the sRGB colour space.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `ColourProfile::Embedded` variant.
+    pub fn srgb() -> Self {
+        ColourProfile::Embedded(srgb_profile())
+    }
+
+    // the bytes stored in the file, with linked paths null terminated
+    fn bytes(&self) -> Vec<u8> {
+        match self {
+            ColourProfile::Embedded(data) => data.clone(),
+            ColourProfile::Linked(path) => {
+                // paths are stored in a single byte code page, so characters outside it are replaced
+                let mut bytes: Vec<u8> = path.chars().map(|c| u8::try_from(c).unwrap_or(b'?')).collect();
+                bytes.push(0);
+                bytes
+            }
+        }
+    }
+}
+
+impl Bmp {
+
+    /// Attaches a colour profile to the image, replacing any existing one.
+    ///
+    /// Older info headers are upgraded to V5, the only version able to reference a profile. The profile is written
+    /// straight after the pixel data.
+    ///
+    /// # Arguments
+    ///
+    /// * `profile` - The profile to embed or link.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::fs::File;
+    /// use std::io;
+    /// use bumpy::bmp::Bmp;
+    /// use bumpy::bmp::colour_profile::ColourProfile;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut file = File::open("sample4.bmp")?;
+    ///     let mut bmp = Bmp::build_from_file(&mut file)?;
+    ///
+    ///     bmp.set_colour_profile(ColourProfile::srgb());
+    ///
+    ///     bmp.write_to_file("test_set_colour_profile")?;
+    ///
+    ///     let mut file = File::open("test_set_colour_profile.bmp")?;
+    ///     let bmp = Bmp::build_from_file(&mut file)?;
+    ///     std::fs::remove_file("test_set_colour_profile.bmp")?;
+    ///
+    ///     assert_eq!(bmp.colour_profile, Some(ColourProfile::srgb()));
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn set_colour_profile(&mut self, profile: ColourProfile) {
+        // grow the header to V5, keeping any V4 fields already present
+        if self.info_header.extra.len() < 84 {
+            let compression = u32::from_le_bytes(self.info_header.compression);
+            let mask_count = match compression {
+                BI_BITFIELDS => 3,
+                BI_ALPHABITFIELDS => 4,
+                _ => 0,
+            };
+
+            // a 40-byte header keeps its bit masks straight after it, where a V5 header has fields of its own
+            let masks: Vec<u8> = if self.info_header.extra.is_empty() && self.gap_data.len() >= 4 * mask_count {
+                self.gap_data.drain(..4 * mask_count).collect()
+            }
+            else {
+                Vec::new()
+            };
+
+            self.info_header.extra.resize(84, 0);
+            self.info_header.size = u32::to_le_bytes(124);
+
+            if !masks.is_empty() {
+                self.info_header.extra[..masks.len()].copy_from_slice(&masks);
+            }
+
+            // V4 and V5 headers declare alpha through their own alpha mask
+            if compression == BI_ALPHABITFIELDS {
+                self.info_header.compression = u32::to_le_bytes(BI_BITFIELDS);
+            }
+        }
+
+        let colour_space = match profile {
+            ColourProfile::Embedded(_) => PROFILE_EMBEDDED,
+            ColourProfile::Linked(_) => PROFILE_LINKED,
+        };
+
+        self.info_header.write_extra(CS_TYPE_OFFSET, colour_space);
+
+        if self.info_header.read_extra(INTENT_OFFSET) == Some(0) {
+            // LCS_GM_IMAGES, the perceptual rendering intent
+            self.info_header.write_extra(INTENT_OFFSET, 4);
+        }
+
+        self.colour_profile = Some(profile);
+        self.update_sizes();
+    }
+
+    /// Removes the image's colour profile, marking it as sRGB instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::fs::File;
+    /// use std::io;
+    /// use bumpy::bmp::Bmp;
+    /// use bumpy::bmp::colour_profile::ColourProfile;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut file = File::open("sample4.bmp")?;
+    ///     let mut bmp = Bmp::build_from_file(&mut file)?;
+    ///
+    ///     bmp.set_colour_profile(ColourProfile::Linked(String::from("C:\\profiles\\print.icc")));
+    ///     bmp.remove_colour_profile();
+    ///
+    ///     assert_eq!(bmp.colour_profile, None);
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn remove_colour_profile(&mut self) {
+        if self.colour_profile.take().is_none() {
+            return;
+        }
+
+        self.info_header.write_extra(CS_TYPE_OFFSET, LCS_SRGB);
+        self.info_header.write_extra(PROFILE_DATA_OFFSET, 0);
+        self.info_header.write_extra(PROFILE_SIZE_OFFSET, 0);
+
+        self.update_sizes();
+    }
+
+    // moves the profile referenced by a V5 header out of the gap or trailing data, into `colour_profile`
+    pub(crate) fn take_colour_profile(&mut self) {
+        let Some((offset, size)) = self.info_header.profile_location() else {
+            return;
+        };
+
+        let start = 14 + u64::from(offset);
+        let end = start + u64::from(size);
+
+        let data_offset = u64::from(u32::from_le_bytes(self.header.data_offset));
+        let gap_start = data_offset - self.gap_data.len() as u64;
+        let trailing_start = data_offset + self.pixel_data.data.len() as u64;
+        let trailing_end = trailing_start + self.trailing_data.len() as u64;
+
+        let data: Vec<u8> = if start >= trailing_start && end <= trailing_end {
+            let range = (start - trailing_start) as usize..(end - trailing_start) as usize;
+            self.trailing_data.drain(range).collect()
+        }
+        else if start >= gap_start && end <= data_offset {
+            let range = (start - gap_start) as usize..(end - gap_start) as usize;
+            self.gap_data.drain(range).collect()
+        }
+        else {
+            return;
+        };
+
+        // a profile found anywhere but straight after the pixel data stays there until the image is changed
+        self.colour_profile_position = if start > trailing_start {
+            Some(ProfilePosition::Trailing((start - trailing_start) as usize))
+        }
+        else if start < trailing_start {
+            Some(ProfilePosition::Gap((start - gap_start) as usize))
+        }
+        else {
+            None
+        };
+
+        self.colour_profile = Some(match self.info_header.colour_space_type() {
+            Some(PROFILE_LINKED) => {
+                let path = data.iter().take_while(|&&byte| byte != 0).map(|&byte| char::from(byte)).collect();
+                ColourProfile::Linked(path)
+            }
+            _ => ColourProfile::Embedded(data),
+        });
+    }
+
+    // moves the profile straight after the pixel data and points the V5 header at it, returning the profile's size
+    pub(crate) fn update_colour_profile_location(&mut self, data_offset: u32) -> u32 {
+        self.colour_profile_position = None;
+
+        let Some(profile) = &self.colour_profile else {
+            return 0;
+        };
+
+        let size = profile.bytes().len() as u32;
+        let offset = data_offset + self.pixel_data.data.len() as u32 - 14;
+
+        self.info_header.write_extra(PROFILE_DATA_OFFSET, offset);
+        self.info_header.write_extra(PROFILE_SIZE_OFFSET, size);
+
+        size
+    }
+
+    // the gap data, the bytes straight after the pixel data and the trailing data as written, with the profile among them
+    pub(crate) fn colour_profile_layout(&self) -> (Vec<u8>, Vec<u8>, Vec<u8>) {
+        let profile = self.colour_profile.as_ref().map_or_else(Vec::new, ColourProfile::bytes);
+        let mut gap_data = self.gap_data.clone();
+        let mut trailing_data = self.trailing_data.clone();
+
+        match self.colour_profile_position {
+            Some(ProfilePosition::Gap(at)) => {
+                let at = at.min(gap_data.len());
+                gap_data.splice(at..at, profile);
+                (gap_data, Vec::new(), trailing_data)
+            }
+            Some(ProfilePosition::Trailing(at)) => {
+                let at = at.min(trailing_data.len());
+                trailing_data.splice(at..at, profile);
+                (gap_data, Vec::new(), trailing_data)
+            }
+            None => (gap_data, profile, trailing_data),
+        }
+    }
+}
+
+// builds an ICC v4.3 display profile for sRGB, with its D65 primaries adapted to the D50 profile connection space
+fn srgb_profile() -> Vec<u8> {
+    let fixed = |value: f64| ((value * 65536.0).round() as i32).to_be_bytes();
+    let xyz = |x: f64, y: f64, z: f64| [b"XYZ ".as_slice(), &[0; 4], &fixed(x), &fixed(y), &fixed(z)].concat();
+
+    let text = |value: &str| {
+        let utf16: Vec<u8> = value.encode_utf16().flat_map(u16::to_be_bytes).collect();
+
+        [
+            b"mluc".as_slice(), &[0; 4], &1u32.to_be_bytes(), &12u32.to_be_bytes(),
+            b"enUS", &(utf16.len() as u32).to_be_bytes(), &28u32.to_be_bytes(), &utf16
+        ].concat()
+    };
+
+    // the sRGB transfer function: Y = (aX + b)^g for X >= d, otherwise Y = cX
+    let curve = [
+        b"para".as_slice(), &[0; 4], &3u16.to_be_bytes(), &[0; 2],
+        &fixed(2.4), &fixed(1.0 / 1.055), &fixed(0.055 / 1.055), &fixed(1.0 / 12.92), &fixed(0.04045)
+    ].concat();
+
+    // Bradford adaptation from D65 to D50
+    let adaptation: Vec<u8> = [
+        1.047_811, 0.022_887, -0.050_127,
+        0.029_542, 0.990_484, -0.017_049,
+        -0.009_234, 0.015_044, 0.752_132
+    ]
+        .iter()
+        .flat_map(|&value| fixed(value))
+        .collect();
+
+    let tags: [(&[u8; 4], Vec<u8>); 10] = [
+        (b"desc", text("sRGB")),
+        (b"cprt", text("No copyright, use freely")),
+        (b"wtpt", xyz(0.9642, 1.0, 0.8249)),
+        (b"chad", [b"sf32".as_slice(), &[0; 4], &adaptation].concat()),
+        (b"rXYZ", xyz(0.436_075, 0.222_504, 0.013_932)),
+        (b"gXYZ", xyz(0.385_065, 0.716_879, 0.097_105)),
+        (b"bXYZ", xyz(0.143_080, 0.060_617, 0.714_173)),
+        (b"rTRC", curve.clone()),
+        (b"gTRC", curve.clone()),
+        (b"bTRC", curve),
+    ];
+
+    let mut table = (tags.len() as u32).to_be_bytes().to_vec();
+    let mut data = Vec::new();
+    let data_start = 128 + 4 + 12 * tags.len();
+
+    for (signature, element) in &tags {
+        table.extend_from_slice(*signature);
+        table.extend_from_slice(&((data_start + data.len()) as u32).to_be_bytes());
+        table.extend_from_slice(&(element.len() as u32).to_be_bytes());
+
+        // tag data starts on four byte boundaries
+        data.extend_from_slice(element);
+        data.resize(data.len().next_multiple_of(4), 0);
+    }
+
+    let size = (data_start + data.len()) as u32;
+
+    let header = [
+        &size.to_be_bytes(), &[0; 4], &[4, 0x30, 0, 0], b"mntr".as_slice(), b"RGB ", b"XYZ ",
+        &[0x07, 0xE8, 0, 1, 0, 1, 0, 0, 0, 0, 0, 0], b"acsp", &[0; 24], &[0; 4],
+        &fixed(0.9642), &fixed(1.0), &fixed(0.8249), &[0; 4], &[0; 16], &[0; 28]
+    ].concat();
+
+    [header, table, data].concat()
+}
@@ -0,0 +1,242 @@
+use crate::bmp::Bmp;
+use crate::bmp::convolve::gaussian_weights;
+use crate::bmp::pad::EdgeMode;
+use crate::bmp::utils::rgb_to_greyscale;
+
+/// The absolute differences between one channel of two images.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ChannelError {
+    /// The largest difference between any pair of pixels.
+    pub max: u8,
+    /// The mean difference over every pair of pixels.
+    pub mean: f64,
+}
+
+/// How closely two images of the same size match, measured on their decoded colours.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Comparison {
+    pub red: ChannelError,
+    pub green: ChannelError,
+    pub blue: ChannelError,
+    /// Always zero between images without an alpha channel, whose fourth byte is reserved.
+    pub alpha: ChannelError,
+    /// The peak signal-to-noise ratio of the colour channels in decibels, infinite for identical images.
+    pub psnr: f64,
+    /// The mean structural similarity of the luminance, from -1.0 up to 1.0 for identical images.
+    pub ssim: f64,
+    /// How many pixels differ in at least one channel, counting alpha only where an image has an alpha channel.
+    pub differing_pixels: u64,
+}
+
+impl Bmp {
+
+    /// Compares the decoded colours of this image with another image of the same size.
+    ///
+    /// Images of different bit depths can be compared, as 1-bit and 8-bit images are decoded through their colour
+    /// tables first. Images without an alpha channel are treated as fully opaque, so the reserved fourth byte of 32-bit
+    /// images without one is never compared. Whether an image has an alpha channel is decided by `has_alpha`.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The image to compare against.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Comparison` struct.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::fs::File;
+    /// use std::io;
+    /// use bumpy::bmp::Bmp;
+    /// use bumpy::bmp::pad::EdgeMode;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut file = File::open("sample.bmp")?;
+    ///     let bmp = Bmp::build_from_file(&mut file)?;
+    ///
+    ///     let mut blurred = bmp.clone();
+    ///     blurred.gaussian_blur(1.0, EdgeMode::Replicate);
+    ///
+    ///     let comparison = bmp.compare(&blurred);
+    ///     println!("PSNR {:.2} dB, SSIM {:.4}", comparison.psnr, comparison.ssim);
+    ///
+    ///     assert_eq!(bmp.compare(&bmp).differing_pixels, 0);
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// Only declared alpha channels are compared:
+    ///
+    /// ```
+    /// use bumpy::bmp::Bmp;
+    ///
+    /// let mut bmp = Bmp::builder().size(4, 4).depth(32).build();
+    /// let mut other = bmp.clone();
+    /// other.pixel_data.data[3] = 0;
+    ///
+    /// assert_eq!(bmp.compare(&other).differing_pixels, 1);
+    ///
+    /// // without the bit masks the fourth byte is reserved
+    /// bmp.info_header.compression = u32::to_le_bytes(0);
+    /// other.info_header.compression = u32::to_le_bytes(0);
+    ///
+    /// assert_eq!(bmp.compare(&other).differing_pixels, 0);
+    /// ```
+    pub fn compare(&self, other: &Bmp) -> Comparison {
+        self.check_comparable(other);
+
+        let pixels = self.pixels();
+        let other_pixels = other.pixels();
+        let count = pixels.len() as f64;
+
+        let mut max = [0u8; 4];
+        let mut sums = [0u64; 4];
+        let mut squared_sum = 0u64;
+        let mut differing_pixels = 0;
+
+        for (&(b, g, r, a), &(ob, og, or, oa)) in pixels.iter().zip(other_pixels.iter()) {
+            let errors = [b.abs_diff(ob), g.abs_diff(og), r.abs_diff(or), a.abs_diff(oa)];
+
+            for (channel, &error) in errors.iter().enumerate() {
+                max[channel] = max[channel].max(error);
+                sums[channel] += u64::from(error);
+            }
+
+            squared_sum += errors[..3].iter().map(|&error| u64::from(error).pow(2)).sum::<u64>();
+
+            if errors.iter().any(|&error| error > 0) {
+                differing_pixels += 1;
+            }
+        }
+
+        let channel = |i: usize| ChannelError { max: max[i], mean: sums[i] as f64 / count };
+
+        let mean_squared_error = squared_sum as f64 / (3.0 * count);
+        let psnr = if mean_squared_error == 0.0 {
+            f64::INFINITY
+        }
+        else {
+            10.0 * (255.0 * 255.0 / mean_squared_error).log10()
+        };
+
+        Comparison {
+            red: channel(2),
+            green: channel(1),
+            blue: channel(0),
+            alpha: channel(3),
+            psnr,
+            ssim: self.ssim(&pixels, &other_pixels),
+            differing_pixels
+        }
+    }
+
+    /// Creates an image highlighting where this image differs from another image of the same size.
+    ///
+    /// Matching pixels are shown as a faded greyscale copy of this image, and differing pixels in red, brighter the
+    /// larger the difference in their decoded colours.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The image to compare against.
+    ///
+    /// # Returns
+    ///
+    /// Returns a 24-bit `Bmp` struct the same size as both images.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::fs::File;
+    /// use std::io;
+    /// use bumpy::bmp::Bmp;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut file = File::open("sample.bmp")?;
+    ///     let bmp = Bmp::build_from_file(&mut file)?;
+    ///
+    ///     let mut edited = bmp.clone();
+    ///     edited.fill_rect(100, 100, 50, 50, (255, 255, 255));
+    ///
+    ///     bmp.diff_image(&edited).write_to_file("test")?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn diff_image(&self, other: &Bmp) -> Bmp {
+        self.check_comparable(other);
+
+        let pixels: Vec<(u8, u8, u8, u8)> = self.pixels()
+            .into_iter()
+            .zip(other.pixels())
+            .map(|((b, g, r, a), (ob, og, or, oa))| {
+                let error = b.abs_diff(ob).max(g.abs_diff(og)).max(r.abs_diff(or)).max(a.abs_diff(oa));
+
+                if error == 0 {
+                    let (grey, _, _) = rgb_to_greyscale((b, g, r));
+                    let faded = 160 + grey / 4;
+
+                    (faded, faded, faded, 255)
+                }
+                else {
+                    // small differences are still clearly visible against the faded background
+                    (0, 0, 96 + (u16::from(error) * 159 / 255) as u8, 255)
+                }
+            })
+            .collect();
+
+        let mut diff = Bmp::builder().size(self.width(), self.height()).build();
+        diff.set_pixels(self.width(), self.height(), 24, &pixels);
+        diff.copy_resolution(self);
+
+        diff
+    }
+
+    // panics unless both images have the same dimensions and a supported bit depth
+    fn check_comparable(&self, other: &Bmp) {
+        self.check_supported_depth();
+        other.check_supported_depth();
+
+        if self.width() != other.width() || self.height() != other.height() {
+            panic!("Images must have the same dimensions to be compared");
+        }
+    }
+
+    // mean structural similarity of the luminance, using Gaussian weighted local statistics
+    fn ssim(&self, pixels: &[(u8, u8, u8, u8)], other_pixels: &[(u8, u8, u8, u8)]) -> f64 {
+        const C1: f64 = (0.01 * 255.0) * (0.01 * 255.0);
+        const C2: f64 = (0.03 * 255.0) * (0.03 * 255.0);
+
+        let luminance = |(b, g, r, _): &(u8, u8, u8, u8)| f64::from(rgb_to_greyscale((*b, *g, *r)).0);
+        let weights = gaussian_weights(1.5);
+
+        let (first, second): (Vec<[f64; 3]>, Vec<[f64; 3]>) = pixels.iter()
+            .zip(other_pixels.iter())
+            .map(|(pixel, other_pixel)| {
+                let x = luminance(pixel);
+                let y = luminance(other_pixel);
+
+                ([x, y, x * x], [y * y, x * y, 0.0])
+            })
+            .unzip();
+
+        let first = self.convolve_separable(&first, &weights, EdgeMode::Replicate);
+        let second = self.convolve_separable(&second, &weights, EdgeMode::Replicate);
+
+        let total: f64 = first.iter()
+            .zip(second.iter())
+            .map(|(&[mean_x, mean_y, square_x], &[square_y, product, _])| {
+                let variance_x = square_x - mean_x * mean_x;
+                let variance_y = square_y - mean_y * mean_y;
+                let covariance = product - mean_x * mean_y;
+
+                ((2.0 * mean_x * mean_y + C1) * (2.0 * covariance + C2))
+                    / ((mean_x * mean_x + mean_y * mean_y + C1) * (variance_x + variance_y + C2))
+            })
+            .sum();
+
+        total / pixels.len() as f64
+    }
+}
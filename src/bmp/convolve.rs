@@ -0,0 +1,487 @@
+use crate::bmp::Bmp;
+use crate::bmp::pad::{edge_coordinate, EdgeMode};
+use crate::bmp::utils::clamp_channel;
+
+/// A 2D convolution kernel.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Kernel {
+    pub width: u32,
+    pub height: u32,
+    pub weights: Vec<f64>,
+}
+
+impl Kernel {
+
+    /// Creates a new `Kernel` struct.
+    ///
+    /// # Arguments
+    ///
+    /// * `width` - The width of the kernel. Must be odd so the kernel has a centre.
+    /// * `height` - The height of the kernel. Must be odd so the kernel has a centre.
+    /// * `weights` - The `width * height` weights of the kernel, top row first.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Kernel` struct.
+    pub fn new(width: u32, height: u32, weights: Vec<f64>) -> Self {
+        if width % 2 == 0 || height % 2 == 0 {
+            panic!("Kernel dimensions must be odd");
+        }
+
+        if weights.len() != (width * height) as usize {
+            panic!("Kernel must have width * height weights");
+        }
+
+        Kernel {
+            width,
+            height,
+            weights
+        }
+    }
+
+    /// Creates a 3x3 sharpening kernel.
+    pub fn sharpen() -> Self {
+        Kernel::new(3, 3, vec![
+            0.0, -1.0, 0.0,
+            -1.0, 5.0, -1.0,
+            0.0, -1.0, 0.0,
+        ])
+    }
+
+    /// Creates a 3x3 emboss kernel, lit from the top left.
+    pub fn emboss() -> Self {
+        Kernel::new(3, 3, vec![
+            -2.0, -1.0, 0.0,
+            -1.0, 1.0, 1.0,
+            0.0, 1.0, 2.0,
+        ])
+    }
+
+    /// Creates a 3x3 Laplacian kernel, which responds to edges in every direction.
+    pub fn laplacian() -> Self {
+        Kernel::new(3, 3, vec![
+            0.0, 1.0, 0.0,
+            1.0, -4.0, 1.0,
+            0.0, 1.0, 0.0,
+        ])
+    }
+
+    /// Creates the horizontal and vertical 3x3 Sobel kernels.
+    pub fn sobel() -> (Self, Self) {
+        (
+            Kernel::new(3, 3, vec![
+                -1.0, 0.0, 1.0,
+                -2.0, 0.0, 2.0,
+                -1.0, 0.0, 1.0,
+            ]),
+            Kernel::new(3, 3, vec![
+                -1.0, -2.0, -1.0,
+                0.0, 0.0, 0.0,
+                1.0, 2.0, 1.0,
+            ]),
+        )
+    }
+
+    /// Creates the horizontal and vertical 3x3 Prewitt kernels.
+    pub fn prewitt() -> (Self, Self) {
+        (
+            Kernel::new(3, 3, vec![
+                -1.0, 0.0, 1.0,
+                -1.0, 0.0, 1.0,
+                -1.0, 0.0, 1.0,
+            ]),
+            Kernel::new(3, 3, vec![
+                -1.0, -1.0, -1.0,
+                0.0, 0.0, 0.0,
+                1.0, 1.0, 1.0,
+            ]),
+        )
+    }
+}
+
+/// The gradient operator used for edge detection.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EdgeOperator {
+    Sobel,
+    Prewitt,
+    Laplacian,
+}
+
+impl Bmp {
+
+    /// Convolves the image with the given kernel.
+    ///
+    /// 1-bit and 8-bit images are expanded to 24-bit first, since convolution mixes colours. The alpha channel of 32-bit
+    /// images is left unchanged.
+    ///
+    /// # Arguments
+    ///
+    /// * `kernel` - The kernel to convolve with.
+    /// * `edge` - How pixels beyond the edges of the image are sampled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::fs::File;
+    /// use std::io;
+    /// use bumpy::bmp::Bmp;
+    /// use bumpy::bmp::convolve::Kernel;
+    /// use bumpy::bmp::pad::EdgeMode;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut file = File::open("sample.bmp")?;
+    ///     let mut bmp = Bmp::build_from_file(&mut file)?;
+    ///
+    ///     bmp.convolve(&Kernel::sharpen(), EdgeMode::Replicate);
+    ///
+    ///     bmp.write_to_file("test")?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn convolve(&mut self, kernel: &Kernel, edge: EdgeMode) {
+        let channels = self.channels();
+        let result = self.convolve_channels(&channels, kernel, edge);
+
+        self.set_channels(&result);
+    }
+
+    /// Blurs the image by averaging each pixel with its neighbours in a square.
+    ///
+    /// # Arguments
+    ///
+    /// * `radius` - The distance from the centre pixel to the edge of the square.
+    /// * `edge` - How pixels beyond the edges of the image are sampled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::fs::File;
+    /// use std::io;
+    /// use bumpy::bmp::Bmp;
+    /// use bumpy::bmp::pad::EdgeMode;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut file = File::open("sample.bmp")?;
+    ///     let mut bmp = Bmp::build_from_file(&mut file)?;
+    ///
+    ///     bmp.box_blur(3, EdgeMode::Mirror);
+    ///
+    ///     bmp.write_to_file("test")?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn box_blur(&mut self, radius: u32, edge: EdgeMode) {
+        let size = 2 * radius + 1;
+        let weights = vec![1.0 / f64::from(size); size as usize];
+
+        let channels = self.channels();
+        let result = self.convolve_separable(&channels, &weights, edge);
+
+        self.set_channels(&result);
+    }
+
+    /// Applies a Gaussian blur to the image.
+    ///
+    /// The blur is applied as two one-dimensional passes, so large values of `sigma` stay fast.
+    ///
+    /// # Arguments
+    ///
+    /// * `sigma` - The standard deviation of the Gaussian in pixels.
+    /// * `edge` - How pixels beyond the edges of the image are sampled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::fs::File;
+    /// use std::io;
+    /// use bumpy::bmp::Bmp;
+    /// use bumpy::bmp::pad::EdgeMode;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut file = File::open("sample.bmp")?;
+    ///     let mut bmp = Bmp::build_from_file(&mut file)?;
+    ///
+    ///     bmp.gaussian_blur(2.0, EdgeMode::Replicate);
+    ///
+    ///     bmp.write_to_file("test")?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn gaussian_blur(&mut self, sigma: f64, edge: EdgeMode) {
+        let channels = self.channels();
+        let result = self.convolve_separable(&channels, &gaussian_weights(sigma), edge);
+
+        self.set_channels(&result);
+    }
+
+    /// Sharpens the image with a 3x3 kernel.
+    ///
+    /// # Arguments
+    ///
+    /// * `edge` - How pixels beyond the edges of the image are sampled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::fs::File;
+    /// use std::io;
+    /// use bumpy::bmp::Bmp;
+    /// use bumpy::bmp::pad::EdgeMode;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut file = File::open("sample.bmp")?;
+    ///     let mut bmp = Bmp::build_from_file(&mut file)?;
+    ///
+    ///     bmp.sharpen(EdgeMode::Replicate);
+    ///
+    ///     bmp.write_to_file("test")?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn sharpen(&mut self, edge: EdgeMode) {
+        self.convolve(&Kernel::sharpen(), edge);
+    }
+
+    /// Sharpens the image by adding back the difference between it and a Gaussian blurred copy.
+    ///
+    /// # Arguments
+    ///
+    /// * `sigma` - The standard deviation of the blur in pixels, controlling the size of the details enhanced.
+    /// * `amount` - How strongly the difference is added back, where 1.0 adds it at full strength.
+    /// * `edge` - How pixels beyond the edges of the image are sampled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::fs::File;
+    /// use std::io;
+    /// use bumpy::bmp::Bmp;
+    /// use bumpy::bmp::pad::EdgeMode;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut file = File::open("sample.bmp")?;
+    ///     let mut bmp = Bmp::build_from_file(&mut file)?;
+    ///
+    ///     bmp.unsharp_mask(1.5, 0.8, EdgeMode::Replicate);
+    ///
+    ///     bmp.write_to_file("test")?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn unsharp_mask(&mut self, sigma: f64, amount: f64, edge: EdgeMode) {
+        let channels = self.channels();
+        let blurred = self.convolve_separable(&channels, &gaussian_weights(sigma), edge);
+
+        let result: Vec<[f64; 3]> = channels.iter()
+            .zip(blurred.iter())
+            .map(|(original, blurred)| {
+                let mut sharpened = [0.0; 3];
+
+                for c in 0..3 {
+                    sharpened[c] = original[c] + amount * (original[c] - blurred[c]);
+                }
+
+                sharpened
+            })
+            .collect();
+
+        self.set_channels(&result);
+    }
+
+    /// Embosses the image, making it look raised and lit from the top left.
+    ///
+    /// # Arguments
+    ///
+    /// * `edge` - How pixels beyond the edges of the image are sampled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::fs::File;
+    /// use std::io;
+    /// use bumpy::bmp::Bmp;
+    /// use bumpy::bmp::pad::EdgeMode;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut file = File::open("sample.bmp")?;
+    ///     let mut bmp = Bmp::build_from_file(&mut file)?;
+    ///
+    ///     bmp.emboss(EdgeMode::Replicate);
+    ///
+    ///     bmp.write_to_file("test")?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn emboss(&mut self, edge: EdgeMode) {
+        self.convolve(&Kernel::emboss(), edge);
+    }
+
+    /// Replaces the image with the strength of the edges in it, so edges appear bright on a black background.
+    ///
+    /// Gradient operators combine their horizontal and vertical responses into a magnitude. Each colour channel is
+    /// processed separately.
+    ///
+    /// # Arguments
+    ///
+    /// * `operator` - The edge detection operator to use.
+    /// * `edge` - How pixels beyond the edges of the image are sampled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::fs::File;
+    /// use std::io;
+    /// use bumpy::bmp::Bmp;
+    /// use bumpy::bmp::convolve::EdgeOperator;
+    /// use bumpy::bmp::pad::EdgeMode;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut file = File::open("sample.bmp")?;
+    ///     let mut bmp = Bmp::build_from_file(&mut file)?;
+    ///
+    ///     bmp.detect_edges(EdgeOperator::Sobel, EdgeMode::Replicate);
+    ///
+    ///     bmp.write_to_file("test")?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn detect_edges(&mut self, operator: EdgeOperator, edge: EdgeMode) {
+        let channels = self.channels();
+
+        let (hor, vert) = match operator {
+            EdgeOperator::Sobel => Kernel::sobel(),
+            EdgeOperator::Prewitt => Kernel::prewitt(),
+            EdgeOperator::Laplacian => {
+                let result: Vec<[f64; 3]> = self.convolve_channels(&channels, &Kernel::laplacian(), edge)
+                    .into_iter()
+                    .map(|[b, g, r]| [b.abs(), g.abs(), r.abs()])
+                    .collect();
+
+                self.set_channels(&result);
+                return;
+            }
+        };
+
+        let gx = self.convolve_channels(&channels, &hor, edge);
+        let gy = self.convolve_channels(&channels, &vert, edge);
+
+        let result: Vec<[f64; 3]> = gx.iter()
+            .zip(gy.iter())
+            .map(|(gx, gy)| {
+                let mut magnitude = [0.0; 3];
+
+                for c in 0..3 {
+                    magnitude[c] = gx[c].hypot(gy[c]);
+                }
+
+                magnitude
+            })
+            .collect();
+
+        self.set_channels(&result);
+    }
+
+    // decodes the image into BGR channel values, top row first
+    fn channels(&self) -> Vec<[f64; 3]> {
+        self.check_supported_depth();
+
+        self.pixels()
+            .into_iter()
+            .map(|(b, g, r, _)| [f64::from(b), f64::from(g), f64::from(r)])
+            .collect()
+    }
+
+    // stores BGR channel values back into the image, keeping the alpha of 32-bit images
+    fn set_channels(&mut self, channels: &[[f64; 3]]) {
+        let bits_per_px = if self.bits_per_px() == 32 { 32 } else { 24 };
+
+        let pixels: Vec<(u8, u8, u8, u8)> = self.pixels()
+            .into_iter()
+            .zip(channels.iter())
+            .map(|((_, _, _, a), &[b, g, r])| (clamp_channel(b), clamp_channel(g), clamp_channel(r), a))
+            .collect();
+
+        self.set_pixels(self.width(), self.height(), bits_per_px, &pixels);
+    }
+
+    // convolves decoded channels with a 2D kernel
+    fn convolve_channels(&self, channels: &[[f64; 3]], kernel: &Kernel, edge: EdgeMode) -> Vec<[f64; 3]> {
+        let width = self.width();
+        let height = self.height();
+        let fill = edge_fill(edge);
+
+        let half_width = i64::from(kernel.width / 2);
+        let half_height = i64::from(kernel.height / 2);
+
+        let mut out = vec![[0.0; 3]; channels.len()];
+
+        for y in 0..i64::from(height) {
+            for x in 0..i64::from(width) {
+                let mut sum = [0.0; 3];
+
+                for ky in 0..i64::from(kernel.height) {
+                    let src_y = edge_coordinate(y + ky - half_height, height, edge);
+
+                    for kx in 0..i64::from(kernel.width) {
+                        let src_x = edge_coordinate(x + kx - half_width, width, edge);
+                        let weight = kernel.weights[(ky * i64::from(kernel.width) + kx) as usize];
+
+                        let src = match src_x.zip(src_y) {
+                            Some((src_x, src_y)) => channels[(src_x + width * src_y) as usize],
+                            None => fill,
+                        };
+
+                        for c in 0..3 {
+                            sum[c] += src[c] * weight;
+                        }
+                    }
+                }
+
+                out[(x + i64::from(width) * y) as usize] = sum;
+            }
+        }
+
+        out
+    }
+
+    // convolves decoded channels with the same 1D kernel horizontally and then vertically
+    pub(crate) fn convolve_separable(&self, channels: &[[f64; 3]], weights: &[f64], edge: EdgeMode) -> Vec<[f64; 3]> {
+        let len = weights.len() as u32;
+
+        let horizontal = self.convolve_channels(channels, &Kernel::new(len, 1, weights.to_vec()), edge);
+
+        self.convolve_channels(&horizontal, &Kernel::new(1, len, weights.to_vec()), edge)
+    }
+}
+
+// BGR channel values used for pixels outside the image in solid colour mode
+fn edge_fill(edge: EdgeMode) -> [f64; 3] {
+    match edge {
+        EdgeMode::Colour(r, g, b) => [f64::from(b), f64::from(g), f64::from(r)],
+        _ => [0.0; 3],
+    }
+}
+
+// normalised 1D Gaussian weights extending three standard deviations either side of the centre
+pub(crate) fn gaussian_weights(sigma: f64) -> Vec<f64> {
+    if sigma <= 0.0 {
+        panic!("Sigma must be greater than zero");
+    }
+
+    let radius = (3.0 * sigma).ceil() as i64;
+
+    let weights: Vec<f64> = (-radius..=radius)
+        .map(|i| (-((i * i) as f64) / (2.0 * sigma * sigma)).exp())
+        .collect();
+
+    let total: f64 = weights.iter().sum();
+
+    weights.into_iter().map(|weight| weight / total).collect()
+}
@@ -0,0 +1,600 @@
+use crate::bmp::Bmp;
+
+impl Bmp {
+
+    /// Draws a one pixel wide line between two points using Bresenham's algorithm.
+    ///
+    /// Like the other drawing functions, coordinates are measured from the top-left corner of the image and anything
+    /// outside the image is clipped. On 1-bit and 8-bit images the colour is added to the colour table if there is
+    /// room, otherwise the closest existing entry is used.
+    ///
+    /// # Arguments
+    ///
+    /// * `start` - The `(x, y)` coordinates of the first end of the line.
+    /// * `end` - The `(x, y)` coordinates of the second end of the line.
+    /// * `colour` - The colour of the line as a `(red, green, blue)` tuple.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::fs::File;
+    /// use std::io;
+    /// use bumpy::bmp::Bmp;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut file = File::open("sample.bmp")?;
+    ///     let mut bmp = Bmp::build_from_file(&mut file)?;
+    ///
+    ///     bmp.draw_line((10, 10), (500, 300), (255, 0, 0));
+    ///     bmp.draw_line((i32::MIN, 0), (i32::MAX, 300), (0, 0, 255));
+    ///
+    ///     bmp.write_to_file("test")?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn draw_line(&mut self, start: (i32, i32), end: (i32, i32), colour: (u8, u8, u8)) {
+        // only walk the part of the line inside the image, however far outside its ends lie
+        let ((mut x, mut y), (end_x, end_y)) = match clip_line(start, end, self.width(), self.height()) {
+            Some(segment) => segment,
+            None => return,
+        };
+
+        let dx = (end_x - x).abs();
+        let dy = -(end_y - y).abs();
+        let step_x = if x < end_x { 1 } else { -1 };
+        let step_y = if y < end_y { 1 } else { -1 };
+        let mut error = dx + dy;
+
+        loop {
+            self.plot(x, y, colour);
+
+            if x == end_x && y == end_y {
+                break;
+            }
+
+            let doubled = 2 * error;
+
+            if doubled >= dy {
+                error += dy;
+                x += step_x;
+            }
+
+            if doubled <= dx {
+                error += dx;
+                y += step_y;
+            }
+        }
+    }
+
+    /// Draws an anti-aliased line between two points using Xiaolin Wu's algorithm.
+    ///
+    /// Edge pixels are blended with the image underneath according to how much of them the line covers.
+    ///
+    /// # Arguments
+    ///
+    /// * `start` - The `(x, y)` coordinates of the first end of the line.
+    /// * `end` - The `(x, y)` coordinates of the second end of the line.
+    /// * `colour` - The colour of the line as a `(red, green, blue)` tuple.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::fs::File;
+    /// use std::io;
+    /// use bumpy::bmp::Bmp;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut file = File::open("sample.bmp")?;
+    ///     let mut bmp = Bmp::build_from_file(&mut file)?;
+    ///
+    ///     bmp.draw_line_aa((10.0, 10.0), (500.0, 300.5), (255, 255, 255));
+    ///
+    ///     bmp.write_to_file("test")?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn draw_line_aa(&mut self, start: (f64, f64), end: (f64, f64), colour: (u8, u8, u8)) {
+        // only walk the part of the line near the image, leaving room for the partly covered pixels along its edges
+        let max = (f64::from(self.width()) + 1.0, f64::from(self.height()) + 1.0);
+
+        let ((mut x0, mut y0), (mut x1, mut y1)) = match clip_segment(start, end, (-2.0, -2.0), max) {
+            Some(segment) => segment,
+            None => return,
+        };
+
+        // walk along the longer axis, swapping coordinates for steep lines
+        let steep = (y1 - y0).abs() > (x1 - x0).abs();
+
+        if steep {
+            std::mem::swap(&mut x0, &mut y0);
+            std::mem::swap(&mut x1, &mut y1);
+        }
+
+        if x0 > x1 {
+            std::mem::swap(&mut x0, &mut x1);
+            std::mem::swap(&mut y0, &mut y1);
+        }
+
+        let gradient = if x1 == x0 { 1.0 } else { (y1 - y0) / (x1 - x0) };
+        let fract = |value: f64| value - value.floor();
+
+        let plot = |bmp: &mut Bmp, major: f64, minor: f64, coverage: f64| {
+            let (x, y) = if steep { (minor, major) } else { (major, minor) };
+            bmp.plot_blended(x as i64, y as i64, colour, coverage);
+        };
+
+        // first end point
+        let end_x = x0.round();
+        let end_y = y0 + gradient * (end_x - x0);
+        let gap = 1.0 - fract(x0 + 0.5);
+        let first_x = end_x;
+
+        plot(self, first_x, end_y.floor(), (1.0 - fract(end_y)) * gap);
+        plot(self, first_x, end_y.floor() + 1.0, fract(end_y) * gap);
+
+        let mut inter_y = end_y + gradient;
+
+        // second end point
+        let end_x = x1.round();
+        let end_y = y1 + gradient * (end_x - x1);
+        let gap = fract(x1 + 0.5);
+        let last_x = end_x;
+
+        plot(self, last_x, end_y.floor(), (1.0 - fract(end_y)) * gap);
+        plot(self, last_x, end_y.floor() + 1.0, fract(end_y) * gap);
+
+        // pixels in between
+        let mut x = first_x + 1.0;
+
+        while x < last_x {
+            plot(self, x, inter_y.floor(), 1.0 - fract(inter_y));
+            plot(self, x, inter_y.floor() + 1.0, fract(inter_y));
+
+            inter_y += gradient;
+            x += 1.0;
+        }
+    }
+
+    /// Draws the one pixel wide outline of a rectangle.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - The column of the left edge of the rectangle.
+    /// * `y` - The row of the top edge of the rectangle.
+    /// * `width` - The width of the rectangle in pixels.
+    /// * `height` - The height of the rectangle in pixels.
+    /// * `colour` - The colour of the outline as a `(red, green, blue)` tuple.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::fs::File;
+    /// use std::io;
+    /// use bumpy::bmp::Bmp;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut file = File::open("sample.bmp")?;
+    ///     let mut bmp = Bmp::build_from_file(&mut file)?;
+    ///
+    ///     bmp.draw_rect(100, 100, 200, 150, (0, 255, 0));
+    ///
+    ///     bmp.write_to_file("test")?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn draw_rect(&mut self, x: i32, y: i32, width: u32, height: u32, colour: (u8, u8, u8)) {
+        if width == 0 || height == 0 {
+            return;
+        }
+
+        // edges beyond the image are clipped anyway, so keep them just outside it rather than wrapping
+        let right = (i64::from(x) + i64::from(width) - 1).min(i64::from(self.width())) as i32;
+        let bottom = (i64::from(y) + i64::from(height) - 1).min(i64::from(self.height())) as i32;
+
+        self.draw_line((x, y), (right, y), colour);
+        self.draw_line((x, bottom), (right, bottom), colour);
+        self.draw_line((x, y), (x, bottom), colour);
+        self.draw_line((right, y), (right, bottom), colour);
+    }
+
+    /// Fills a rectangle with a solid colour.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - The column of the left edge of the rectangle.
+    /// * `y` - The row of the top edge of the rectangle.
+    /// * `width` - The width of the rectangle in pixels.
+    /// * `height` - The height of the rectangle in pixels.
+    /// * `colour` - The fill colour as a `(red, green, blue)` tuple.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::fs::File;
+    /// use std::io;
+    /// use bumpy::bmp::Bmp;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut file = File::open("sample2.bmp")?;
+    ///     let mut bmp = Bmp::build_from_file(&mut file)?;
+    ///
+    ///     bmp.fill_rect(2, 0, 3, 2, (0, 0, 255));
+    ///
+    ///     bmp.write_to_file("test")?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn fill_rect(&mut self, x: i32, y: i32, width: u32, height: u32, colour: (u8, u8, u8)) {
+        let left = i64::from(x).max(0);
+        let top = i64::from(y).max(0);
+        let right = (i64::from(x) + i64::from(width)).min(i64::from(self.width()));
+        let bottom = (i64::from(y) + i64::from(height)).min(i64::from(self.height()));
+
+        for row in top..bottom {
+            for column in left..right {
+                self.plot(column, row, colour);
+            }
+        }
+    }
+
+    /// Draws the one pixel wide outline of a circle using the midpoint circle algorithm.
+    ///
+    /// # Arguments
+    ///
+    /// * `centre` - The `(x, y)` coordinates of the centre of the circle.
+    /// * `radius` - The radius of the circle in pixels.
+    /// * `colour` - The colour of the outline as a `(red, green, blue)` tuple.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::fs::File;
+    /// use std::io;
+    /// use bumpy::bmp::Bmp;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut file = File::open("sample.bmp")?;
+    ///     let mut bmp = Bmp::build_from_file(&mut file)?;
+    ///
+    ///     bmp.draw_circle((256, 256), 100, (255, 255, 0));
+    ///
+    ///     bmp.write_to_file("test")?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn draw_circle(&mut self, centre: (i32, i32), radius: u32, colour: (u8, u8, u8)) {
+        self.draw_ellipse(centre, radius, radius, colour);
+    }
+
+    /// Draws the one pixel wide outline of an axis-aligned ellipse using the midpoint ellipse algorithm.
+    ///
+    /// # Arguments
+    ///
+    /// * `centre` - The `(x, y)` coordinates of the centre of the ellipse.
+    /// * `radius_x` - The horizontal radius of the ellipse in pixels.
+    /// * `radius_y` - The vertical radius of the ellipse in pixels.
+    /// * `colour` - The colour of the outline as a `(red, green, blue)` tuple.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::fs::File;
+    /// use std::io;
+    /// use bumpy::bmp::Bmp;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut file = File::open("sample.bmp")?;
+    ///     let mut bmp = Bmp::build_from_file(&mut file)?;
+    ///
+    ///     bmp.draw_ellipse((256, 256), 200, 80, (0, 255, 255));
+    ///
+    ///     bmp.write_to_file("test")?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn draw_ellipse(&mut self, centre: (i32, i32), radius_x: u32, radius_y: u32, colour: (u8, u8, u8)) {
+        // a flat ellipse is a straight line, which the midpoint loops below would never finish
+        if radius_x == 0 || radius_y == 0 {
+            let reach = |centre: i32, radius: u32| {
+                let clamp = |value: i64| value.clamp(i64::from(i32::MIN), i64::from(i32::MAX)) as i32;
+                (clamp(i64::from(centre) - i64::from(radius)), clamp(i64::from(centre) + i64::from(radius)))
+            };
+
+            let (left, right) = reach(centre.0, radius_x);
+            let (top, bottom) = reach(centre.1, radius_y);
+
+            self.draw_line((left, top), (right, bottom), colour);
+            return;
+        }
+
+        // squared radii of large ellipses overflow i64, so the decision variables are kept in i128
+        let (cx, cy) = (i128::from(centre.0), i128::from(centre.1));
+        let (a, b) = (i128::from(radius_x), i128::from(radius_y));
+
+        let plot_quadrants = |bmp: &mut Bmp, x: i128, y: i128| {
+            bmp.plot((cx + x) as i64, (cy + y) as i64, colour);
+            bmp.plot((cx - x) as i64, (cy + y) as i64, colour);
+            bmp.plot((cx + x) as i64, (cy - y) as i64, colour);
+            bmp.plot((cx - x) as i64, (cy - y) as i64, colour);
+        };
+
+        let (a2, b2) = (a * a, b * b);
+        let (mut x, mut y) = (0, b);
+
+        // region where the slope is shallower than -1, stepping along x
+        let mut decision = b2 - a2 * b + a2 / 4;
+
+        while b2 * x <= a2 * y {
+            plot_quadrants(self, x, y);
+
+            if decision < 0 {
+                decision += b2 * (2 * x + 3);
+            }
+            else {
+                decision += b2 * (2 * x + 3) + a2 * (2 - 2 * y);
+                y -= 1;
+            }
+
+            x += 1;
+        }
+
+        // region where the slope is steeper than -1, stepping along y
+        let (mut x, mut y) = (a, 0);
+        let mut decision = a2 - b2 * a + b2 / 4;
+
+        while a2 * y <= b2 * x {
+            plot_quadrants(self, x, y);
+
+            if decision < 0 {
+                decision += a2 * (2 * y + 3);
+            }
+            else {
+                decision += a2 * (2 * y + 3) + b2 * (2 - 2 * x);
+                x -= 1;
+            }
+
+            y += 1;
+        }
+    }
+
+    /// Draws the outline of a closed polygon.
+    ///
+    /// # Arguments
+    ///
+    /// * `points` - The `(x, y)` coordinates of the corners of the polygon, in order.
+    /// * `colour` - The colour of the outline as a `(red, green, blue)` tuple.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::fs::File;
+    /// use std::io;
+    /// use bumpy::bmp::Bmp;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut file = File::open("sample.bmp")?;
+    ///     let mut bmp = Bmp::build_from_file(&mut file)?;
+    ///
+    ///     bmp.draw_polygon(&[(50, 50), (200, 80), (120, 220)], (255, 0, 255));
+    ///
+    ///     bmp.write_to_file("test")?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn draw_polygon(&mut self, points: &[(i32, i32)], colour: (u8, u8, u8)) {
+        for (i, &start) in points.iter().enumerate() {
+            let end = points[(i + 1) % points.len()];
+            self.draw_line(start, end, colour);
+        }
+    }
+
+    /// Fills a polygon with a solid colour, using the even-odd rule for self-intersecting shapes.
+    ///
+    /// # Arguments
+    ///
+    /// * `points` - The `(x, y)` coordinates of the corners of the polygon, in order.
+    /// * `colour` - The fill colour as a `(red, green, blue)` tuple.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::fs::File;
+    /// use std::io;
+    /// use bumpy::bmp::Bmp;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut file = File::open("sample.bmp")?;
+    ///     let mut bmp = Bmp::build_from_file(&mut file)?;
+    ///
+    ///     bmp.fill_polygon(&[(50, 50), (200, 80), (120, 220)], (255, 0, 255));
+    ///
+    ///     bmp.write_to_file("test")?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn fill_polygon(&mut self, points: &[(i32, i32)], colour: (u8, u8, u8)) {
+        if points.len() < 3 {
+            return;
+        }
+
+        let top = points.iter().map(|&(_, y)| y).min().unwrap_or(0).max(0);
+        let bottom = points.iter().map(|&(_, y)| y).max().unwrap_or(0).min(self.height() as i32 - 1);
+
+        for y in top..=bottom {
+            // sample through the centre of each row so shared vertices are only counted once
+            let scan_y = f64::from(y) + 0.5;
+            let mut crossings = Vec::new();
+
+            for (i, &(x0, y0)) in points.iter().enumerate() {
+                let (x1, y1) = points[(i + 1) % points.len()];
+                let (x0, y0, x1, y1) = (f64::from(x0), f64::from(y0), f64::from(x1), f64::from(y1));
+
+                if (y0 <= scan_y) != (y1 <= scan_y) {
+                    crossings.push(x0 + (scan_y - y0) * (x1 - x0) / (y1 - y0));
+                }
+            }
+
+            crossings.sort_by(f64::total_cmp);
+
+            for pair in crossings.chunks_exact(2) {
+                let start = (pair[0] - 0.5).ceil() as i64;
+                let end = (pair[1] - 0.5).floor() as i64;
+
+                for x in start.max(0)..=end.min(i64::from(self.width()) - 1) {
+                    self.plot(x, i64::from(y), colour);
+                }
+            }
+        }
+    }
+
+    /// Fills the region of identically coloured pixels connected to a starting pixel.
+    ///
+    /// Pixels are connected horizontally and vertically, not diagonally.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - The column of the starting pixel.
+    /// * `y` - The row of the starting pixel, counted from the top.
+    /// * `colour` - The fill colour as a `(red, green, blue)` tuple.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::fs::File;
+    /// use std::io;
+    /// use bumpy::bmp::Bmp;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut file = File::open("sample4.bmp")?;
+    ///     let mut bmp = Bmp::build_from_file(&mut file)?;
+    ///
+    ///     bmp.flood_fill(5, 150, (255, 255, 255));
+    ///
+    ///     bmp.write_to_file("test")?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn flood_fill(&mut self, x: u32, y: u32, colour: (u8, u8, u8)) {
+        let width = self.width();
+        let height = self.height();
+
+        if x >= width || y >= height {
+            return;
+        }
+
+        let target = self.read_pixel(x, y);
+        let mut visited = vec![false; (width * height) as usize];
+        let mut stack = vec![(x, y)];
+
+        while let Some((x, y)) = stack.pop() {
+            let index = (x + width * y) as usize;
+
+            if visited[index] || self.read_pixel(x, y) != target {
+                continue;
+            }
+
+            visited[index] = true;
+            self.plot(i64::from(x), i64::from(y), colour);
+
+            if x > 0 {
+                stack.push((x - 1, y));
+            }
+
+            if x + 1 < width {
+                stack.push((x + 1, y));
+            }
+
+            if y > 0 {
+                stack.push((x, y - 1));
+            }
+
+            if y + 1 < height {
+                stack.push((x, y + 1));
+            }
+        }
+    }
+
+    // sets the pixel at (x, y) to an RGB colour, ignoring coordinates outside the image
+    fn plot(&mut self, x: i64, y: i64, colour: (u8, u8, u8)) {
+        if x < 0 || y < 0 || x >= i64::from(self.width()) || y >= i64::from(self.height()) {
+            return;
+        }
+
+        let (r, g, b) = colour;
+        self.write_pixel(x as u32, y as u32, (b, g, r, 255));
+    }
+
+    // mixes an RGB colour into the pixel at (x, y) in proportion to `coverage`, ignoring coordinates outside the image
+    fn plot_blended(&mut self, x: i64, y: i64, colour: (u8, u8, u8), coverage: f64) {
+        if x < 0 || y < 0 || x >= i64::from(self.width()) || y >= i64::from(self.height()) || coverage <= 0.0 {
+            return;
+        }
+
+        let (r, g, b) = colour;
+        let (old_b, old_g, old_r, a) = self.read_pixel(x as u32, y as u32);
+        let coverage = coverage.min(1.0);
+
+        let mix = |new: u8, old: u8| (f64::from(new) * coverage + f64::from(old) * (1.0 - coverage)).round() as u8;
+
+        self.write_pixel(x as u32, y as u32, (mix(b, old_b), mix(g, old_g), mix(r, old_r), a.max((coverage * 255.0) as u8)));
+    }
+}
+
+// clips a line to the image, returning the ends of the visible part rounded to whole pixels
+fn clip_line(start: (i32, i32), end: (i32, i32), width: u32, height: u32) -> Option<((i64, i64), (i64, i64))> {
+    if width == 0 || height == 0 {
+        return None;
+    }
+
+    let start = (f64::from(start.0), f64::from(start.1));
+    let end = (f64::from(end.0), f64::from(end.1));
+    let (start, end) = clip_segment(start, end, (0.0, 0.0), (f64::from(width - 1), f64::from(height - 1)))?;
+
+    let point = |(x, y): (f64, f64)| (x.round() as i64, y.round() as i64);
+
+    Some((point(start), point(end)))
+}
+
+// clips a line to the box from `min` to `max` using the Liang-Barsky algorithm, returning the ends of the part inside
+fn clip_segment(start: (f64, f64), end: (f64, f64), min: (f64, f64), max: (f64, f64)) -> Option<((f64, f64), (f64, f64))> {
+    let (x0, y0) = start;
+    let (dx, dy) = (end.0 - x0, end.1 - y0);
+
+    let (mut enter, mut leave) = (0.0, 1.0);
+
+    // each edge as the rate the line moves towards its outside and the distance to it from the start
+    for (towards, distance) in [(-dx, x0 - min.0), (dx, max.0 - x0), (-dy, y0 - min.1), (dy, max.1 - y0)] {
+        if towards == 0.0 {
+            if distance < 0.0 {
+                return None;
+            }
+        }
+        else {
+            let t = distance / towards;
+
+            if towards < 0.0 {
+                enter = f64::max(enter, t);
+            }
+            else {
+                leave = f64::min(leave, t);
+            }
+        }
+    }
+
+    if enter > leave {
+        return None;
+    }
+
+    let point = |t: f64| (x0 + t * dx, y0 + t * dy);
+
+    Some((point(enter), point(leave)))
+}
@@ -0,0 +1,140 @@
+use std::hash::{Hash, Hasher};
+use crate::bmp::Bmp;
+
+impl Bmp {
+
+    /// Checks whether two images are byte-for-byte identical, including every header field, the colour table and any
+    /// row padding.
+    ///
+    /// Use `==` instead to compare what the images look like.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The image to compare against.
+    ///
+    /// # Returns
+    ///
+    /// Returns `true` if both images would be written to identical files.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::fs::File;
+    /// use std::io;
+    /// use bumpy::bmp::Bmp;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut file = File::open("sample8.bmp")?;
+    ///     let bmp = Bmp::build_from_file(&mut file)?;
+    ///
+    ///     // change a padding byte at the end of the first row
+    ///     let mut padded = bmp.clone();
+    ///     padded.pixel_data.data[9] = 0xFF;
+    ///
+    ///     assert!(bmp == padded);
+    ///     assert!(!bmp.identical(&padded));
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn identical(&self, other: &Bmp) -> bool {
+        self.header == other.header
+            && self.info_header == other.info_header
+            && self.colour_table == other.colour_table
+            && self.gap_data == other.gap_data
+            && self.pixel_data == other.pixel_data
+            && self.colour_profile == other.colour_profile
+            && self.trailing_data == other.trailing_data
+            && self.colour_profile_position == other.colour_profile_position
+    }
+
+    /// Computes a hash of the image's dimensions and decoded pixels, for deduplicating images.
+    ///
+    /// Images that compare equal with `==` have the same hash, whatever their bit depth or header fields. The hash is
+    /// the 64-bit FNV-1a hash of the content, so it is stable between runs, platforms and versions of this crate.
+    ///
+    /// # Returns
+    ///
+    /// Returns the hash as a `u64`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::fs::File;
+    /// use std::io;
+    /// use bumpy::bmp::Bmp;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut file = File::open("sample.bmp")?;
+    ///     let bmp = Bmp::build_from_file(&mut file)?;
+    ///
+    ///     let mut flipped = bmp.clone();
+    ///     flipped.flip_hor();
+    ///
+    ///     assert_eq!(bmp.content_hash(), bmp.clone().content_hash());
+    ///     assert_ne!(bmp.content_hash(), flipped.content_hash());
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn content_hash(&self) -> u64 {
+        let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+
+        self.write_content(|bytes| {
+            for &byte in bytes {
+                hash ^= u64::from(byte);
+                hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+            }
+        });
+
+        hash
+    }
+
+    // whether every pixel can be decoded, so images can be compared by colour rather than by their bytes
+    fn is_decodable(&self) -> bool {
+        let bits_per_px = self.bits_per_px();
+
+        (bits_per_px == 1 || bits_per_px == 8 || bits_per_px == 24 || bits_per_px == 32)
+            && self.pixel_data.data.len() >= self.row_size() * self.height() as usize
+    }
+
+    // feeds the dimensions and decoded BGRA pixels to `write`, or the raw bytes of images that cannot be decoded
+    fn write_content<F: FnMut(&[u8])>(&self, mut write: F) {
+        if !self.is_decodable() {
+            write(&self.header.signature);
+            write(&self.info_header.bits_per_px);
+            write(&self.pixel_data.data);
+            return;
+        }
+
+        write(&self.width().to_le_bytes());
+        write(&self.height().to_le_bytes());
+
+        let pixels: Vec<u8> = self.pixels()
+            .into_iter()
+            .flat_map(|(b, g, r, a)| [b, g, r, a])
+            .collect();
+
+        write(&pixels);
+    }
+}
+
+/// Images are equal when they have the same dimensions and decoded colours, regardless of bit depth, colour table
+/// order, row padding or header fields such as resolution. Use `Bmp::identical` for a byte-for-byte comparison.
+impl PartialEq for Bmp {
+    fn eq(&self, other: &Self) -> bool {
+        if !self.is_decodable() || !other.is_decodable() {
+            return self.identical(other);
+        }
+
+        self.width() == other.width() && self.height() == other.height() && self.pixels() == other.pixels()
+    }
+}
+
+impl Eq for Bmp {}
+
+impl Hash for Bmp {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.write_content(|bytes| state.write(bytes));
+    }
+}
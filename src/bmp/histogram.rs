@@ -0,0 +1,423 @@
+use crate::bmp::Bmp;
+use crate::bmp::utils::{clamp_channel, rgb_to_greyscale};
+
+/// Per-channel and luminance pixel counts for an image.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Histogram {
+    pub red: [u32; 256],
+    pub green: [u32; 256],
+    pub blue: [u32; 256],
+    pub luminance: [u32; 256],
+    /// How many pixels use each colour table entry. Only present for 1-bit and 8-bit images.
+    pub indices: Option<[u32; 256]>,
+}
+
+impl Histogram {
+    // value below which `fraction` of the counts in `channel` fall
+    fn percentile(channel: &[u32; 256], fraction: f64) -> u8 {
+        let total: u32 = channel.iter().sum();
+        let target = (f64::from(total) * fraction).floor() as u32;
+
+        let mut count = 0;
+
+        for (value, &entries) in channel.iter().enumerate() {
+            count += entries;
+
+            if count > target {
+                return value as u8;
+            }
+        }
+
+        255
+    }
+}
+
+impl Bmp {
+
+    /// Counts how many pixels take each value in the red, green, blue and luminance channels.
+    ///
+    /// For 1-bit and 8-bit images the colour table indices are counted and then mapped through the colour table, so the channel
+    /// counts describe the decoded colours.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Histogram` struct.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::fs::File;
+    /// use std::io;
+    /// use bumpy::bmp::Bmp;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut file = File::open("sample2.bmp")?;
+    ///     let bmp = Bmp::build_from_file(&mut file)?;
+    ///
+    ///     let histogram = bmp.histogram();
+    ///     println!("{:?}", histogram.luminance);
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn histogram(&self) -> Histogram {
+        let mut histogram = Histogram {
+            red: [0; 256],
+            green: [0; 256],
+            blue: [0; 256],
+            luminance: [0; 256],
+            indices: None,
+        };
+
+        let mut add = |(b, g, r): (u8, u8, u8), count: u32| {
+            histogram.blue[b as usize] += count;
+            histogram.green[g as usize] += count;
+            histogram.red[r as usize] += count;
+            histogram.luminance[rgb_to_greyscale((b, g, r)).0 as usize] += count;
+        };
+
+        let bits_per_px = self.bits_per_px();
+
+        if bits_per_px == 1 || bits_per_px == 8 {
+            let mut indices = [0; 256];
+
+            for index in self.indices() {
+                indices[index as usize] += 1;
+            }
+
+            for (index, &count) in indices.iter().enumerate() {
+                if count > 0 {
+                    let (b, g, r, _) = self.palette_colour(index as u8);
+                    add((b, g, r), count);
+                }
+            }
+
+            histogram.indices = Some(indices);
+        }
+        else {
+            for (b, g, r, _) in self.pixels() {
+                add((b, g, r), 1);
+            }
+        }
+
+        histogram
+    }
+
+    /// Spreads the luminance of the image evenly across the full range using global histogram equalisation.
+    ///
+    /// Colours keep their chroma, only their brightness changes. 1-bit and 8-bit images are adjusted through their colour table.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::fs::File;
+    /// use std::io;
+    /// use bumpy::bmp::Bmp;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut file = File::open("sample.bmp")?;
+    ///     let mut bmp = Bmp::build_from_file(&mut file)?;
+    ///
+    ///     bmp.equalise();
+    ///
+    ///     bmp.write_to_file("test")?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn equalise(&mut self) {
+        let table = equalisation_table(&self.histogram().luminance.map(f64::from));
+
+        self.map_luminance(&table);
+    }
+
+    /// Stretches each colour channel separately so it spans the full range, ignoring a fraction of outlying pixels.
+    ///
+    /// # Arguments
+    ///
+    /// * `clip` - The fraction of pixels, from 0.0 to 0.5, ignored at each end of every channel.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::fs::File;
+    /// use std::io;
+    /// use bumpy::bmp::Bmp;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut file = File::open("sample.bmp")?;
+    ///     let mut bmp = Bmp::build_from_file(&mut file)?;
+    ///
+    ///     bmp.auto_levels(0.01);
+    ///
+    ///     bmp.write_to_file("test")?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn auto_levels(&mut self, clip: f64) {
+        let histogram = self.histogram();
+
+        let blue = stretch_table(&histogram.blue, clip);
+        let green = stretch_table(&histogram.green, clip);
+        let red = stretch_table(&histogram.red, clip);
+
+        self.map_colours(|(b, g, r)| (blue[b as usize], green[g as usize], red[r as usize]));
+    }
+
+    /// Stretches the luminance of the image so it spans the full range, ignoring a fraction of outlying pixels.
+    ///
+    /// Unlike `auto_levels`, the balance between the colour channels is kept.
+    ///
+    /// # Arguments
+    ///
+    /// * `clip` - The fraction of pixels, from 0.0 to 0.5, ignored at each end of the range.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::fs::File;
+    /// use std::io;
+    /// use bumpy::bmp::Bmp;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut file = File::open("sample.bmp")?;
+    ///     let mut bmp = Bmp::build_from_file(&mut file)?;
+    ///
+    ///     bmp.stretch_contrast(0.005);
+    ///
+    ///     bmp.write_to_file("test")?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn stretch_contrast(&mut self, clip: f64) {
+        let table = stretch_table(&self.histogram().luminance, clip);
+
+        self.map_luminance(&table);
+    }
+
+    /// Applies contrast limited adaptive histogram equalisation (CLAHE) to the luminance of the image.
+    ///
+    /// The image is split into a grid of tiles which are equalised separately, with the result blended between
+    /// neighbouring tiles. Because every pixel is adjusted differently, 1-bit and 8-bit images are expanded to 24-bit first.
+    ///
+    /// # Arguments
+    ///
+    /// * `tiles_x` - The number of tiles across the image.
+    /// * `tiles_y` - The number of tiles down the image.
+    /// * `clip_limit` - How many times the average count a histogram bin may hold before it is clipped. Lower values
+    ///   limit the amplification of noise, down to 1.0 which clips every bin to the average and leaves only a slight
+    ///   adjustment.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::fs::File;
+    /// use std::io;
+    /// use bumpy::bmp::Bmp;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut file = File::open("sample.bmp")?;
+    ///     let mut bmp = Bmp::build_from_file(&mut file)?;
+    ///
+    ///     bmp.clahe(8, 8, 3.0);
+    ///
+    ///     bmp.write_to_file("test")?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// Tile counts that don't divide the image size still give every part of a flat image the same value:
+    ///
+    /// ```
+    /// use bumpy::bmp::Bmp;
+    ///
+    /// let mut bmp = Bmp::builder().size(10, 10).fill((128, 128, 128)).build();
+    ///
+    /// bmp.clahe(8, 8, 4.0);
+    ///
+    /// assert_eq!(bmp.histogram().luminance.iter().filter(|&&count| count > 0).count(), 1);
+    /// ```
+    pub fn clahe(&mut self, tiles_x: u32, tiles_y: u32, clip_limit: f64) {
+        let width = self.width();
+        let height = self.height();
+
+        if tiles_x == 0 || tiles_y == 0 || tiles_x > width || tiles_y > height {
+            panic!("The number of tiles must be between 1 and the size of the image");
+        }
+
+        self.check_supported_depth();
+
+        let bits_per_px = self.bits_per_px();
+
+        let pixels = self.pixels();
+        let luminance: Vec<u8> = pixels.iter().map(|&(b, g, r, _)| rgb_to_greyscale((b, g, r)).0).collect();
+
+        // tiles split the image as evenly as possible, so none are left empty
+        let x_bounds = tile_bounds(width, tiles_x);
+        let y_bounds = tile_bounds(height, tiles_y);
+
+        // equalisation table for every tile, clipped so no bin exceeds the limit
+        let mut tables = Vec::with_capacity((tiles_x * tiles_y) as usize);
+
+        for tile_y in 0..tiles_y as usize {
+            for tile_x in 0..tiles_x as usize {
+                let mut histogram = [0; 256];
+
+                for y in y_bounds[tile_y]..y_bounds[tile_y + 1] {
+                    for x in x_bounds[tile_x]..x_bounds[tile_x + 1] {
+                        histogram[luminance[(x + width * y) as usize] as usize] += 1;
+                    }
+                }
+
+                tables.push(equalisation_table(&clip_histogram(&histogram, clip_limit)));
+            }
+        }
+
+        let columns = tile_neighbours(&x_bounds);
+        let rows = tile_neighbours(&y_bounds);
+
+        let new_pixels: Vec<(u8, u8, u8, u8)> = pixels.iter()
+            .enumerate()
+            .map(|(i, &(b, g, r, a))| {
+                let x = i as u32 % width;
+                let y = i as u32 / width;
+                let value = luminance[i] as usize;
+
+                let (x0, x1, fx) = columns[x as usize];
+                let (y0, y1, fy) = rows[y as usize];
+
+                let mapped = |tile_x: u32, tile_y: u32| f64::from(tables[(tile_x + tiles_x * tile_y) as usize][value]);
+
+                let top = mapped(x0, y0) * (1.0 - fx) + mapped(x1, y0) * fx;
+                let bottom = mapped(x0, y1) * (1.0 - fx) + mapped(x1, y1) * fx;
+                let delta = (top * (1.0 - fy) + bottom * fy) - value as f64;
+
+                let shift = |c: u8| clamp_channel(f64::from(c) + delta);
+
+                (shift(b), shift(g), shift(r), a)
+            })
+            .collect();
+
+        let bits_per_px = if bits_per_px == 32 { 32 } else { 24 };
+
+        self.set_pixels(width, height, bits_per_px, &new_pixels);
+    }
+
+    // remaps the luminance of every colour through `table`, shifting all channels equally so chroma is kept
+    fn map_luminance(&mut self, table: &[u8; 256]) {
+        self.map_colours(|(b, g, r)| {
+            let value = rgb_to_greyscale((b, g, r)).0;
+            let delta = i32::from(table[value as usize]) - i32::from(value);
+
+            let shift = |c: u8| (i32::from(c) + delta).clamp(0, 255) as u8;
+
+            (shift(b), shift(g), shift(r))
+        });
+    }
+}
+
+// start of every tile along an axis, followed by the end of the last one
+fn tile_bounds(len: u32, tiles: u32) -> Vec<u32> {
+    (0..=tiles).map(|tile| (u64::from(tile) * u64::from(len) / u64::from(tiles)) as u32).collect()
+}
+
+// for every pixel along an axis, the tiles whose centres lie either side of it and how far it is between them
+fn tile_neighbours(bounds: &[u32]) -> Vec<(u32, u32, f64)> {
+    let centres: Vec<f64> = bounds.windows(2).map(|tile| f64::from(tile[0] + tile[1]) / 2.0).collect();
+    let last = centres.len() - 1;
+    let mut first = 0;
+
+    (0..bounds[last + 1])
+        .map(|i| {
+            let position = f64::from(i) + 0.5;
+
+            while first < last && centres[first + 1] <= position {
+                first += 1;
+            }
+
+            if position <= centres[0] || first == last {
+                // pixels beyond the outermost centres take that tile's table alone
+                (first as u32, first as u32, 0.0)
+            }
+            else {
+                let fraction = (position - centres[first]) / (centres[first + 1] - centres[first]);
+
+                (first as u32, first as u32 + 1, fraction)
+            }
+        })
+        .collect()
+}
+
+// maps each value to its position in the cumulative distribution of the histogram
+fn equalisation_table(histogram: &[f64; 256]) -> [u8; 256] {
+    let total: f64 = histogram.iter().sum();
+    let mut table = [0; 256];
+
+    if total == 0.0 {
+        return table;
+    }
+
+    // offset by the count of the darkest value present so it maps to black
+    let first = histogram.iter().copied().find(|&count| count > 0.0).unwrap_or(0.0);
+    let mut cumulative = 0.0;
+
+    for (value, &count) in histogram.iter().enumerate() {
+        cumulative += count;
+
+        table[value] = if total == first {
+            value as u8
+        }
+        else {
+            ((cumulative - first).max(0.0) * 255.0 / (total - first)).round() as u8
+        };
+    }
+
+    table
+}
+
+// linearly maps the range between the clipped darkest and lightest values onto 0..=255
+fn stretch_table(histogram: &[u32; 256], clip: f64) -> [u8; 256] {
+    let clip = clip.clamp(0.0, 0.5);
+
+    let low = f64::from(Histogram::percentile(histogram, clip));
+    let high = f64::from(Histogram::percentile(histogram, 1.0 - clip));
+
+    let mut table = [0; 256];
+
+    for (value, entry) in table.iter_mut().enumerate() {
+        *entry = if high > low {
+            clamp_channel((value as f64 - low) * 255.0 / (high - low))
+        }
+        else {
+            value as u8
+        };
+    }
+
+    table
+}
+
+// caps every bin at `clip_limit` times the average count, spreading the excess evenly across all bins
+// counts are kept fractional so tiles of different sizes with the same content get the same table
+fn clip_histogram(histogram: &[u32; 256], clip_limit: f64) -> [f64; 256] {
+    let total: u32 = histogram.iter().sum();
+    let limit = clip_limit * f64::from(total) / 256.0;
+
+    let mut clipped = histogram.map(f64::from);
+    let mut excess = 0.0;
+
+    for count in clipped.iter_mut() {
+        if *count > limit {
+            excess += *count - limit;
+            *count = limit;
+        }
+    }
+
+    for count in clipped.iter_mut() {
+        *count += excess / 256.0;
+    }
+
+    clipped
+}
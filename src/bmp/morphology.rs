@@ -0,0 +1,377 @@
+use crate::bmp::Bmp;
+use crate::bmp::utils::rgb_to_greyscale;
+
+/// The neighbourhood examined around each pixel by a morphological operation.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StructuringElement {
+    pub width: u32,
+    pub height: u32,
+    pub mask: Vec<bool>,
+}
+
+impl StructuringElement {
+
+    /// Creates a new `StructuringElement` struct, centred on its middle pixel.
+    ///
+    /// # Arguments
+    ///
+    /// * `width` - The width of the element.
+    /// * `height` - The height of the element.
+    /// * `mask` - The `width * height` flags marking which pixels belong to the element, top row first.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `StructuringElement` struct.
+    pub fn new(width: u32, height: u32, mask: Vec<bool>) -> Self {
+        if width == 0 || height == 0 {
+            panic!("Structuring element must not be empty");
+        }
+
+        if mask.len() != (width * height) as usize {
+            panic!("Structuring element must have width * height entries");
+        }
+
+        StructuringElement {
+            width,
+            height,
+            mask
+        }
+    }
+
+    /// Creates a solid rectangular structuring element.
+    pub fn rect(width: u32, height: u32) -> Self {
+        StructuringElement::new(width, height, vec![true; (width * height) as usize])
+    }
+
+    /// Creates a cross shaped structuring element covering the middle row and column.
+    pub fn cross(width: u32, height: u32) -> Self {
+        let mask = (0..width * height)
+            .map(|i| i % width == width / 2 || i / width == height / 2)
+            .collect();
+
+        StructuringElement::new(width, height, mask)
+    }
+
+    /// Creates an elliptical structuring element that fits inside the given dimensions.
+    pub fn ellipse(width: u32, height: u32) -> Self {
+        let radius_x = f64::from(width) / 2.0;
+        let radius_y = f64::from(height) / 2.0;
+
+        let mask = (0..width * height)
+            .map(|i| {
+                let dx = (f64::from(i % width) + 0.5 - radius_x) / radius_x;
+                let dy = (f64::from(i / width) + 0.5 - radius_y) / radius_y;
+
+                dx * dx + dy * dy <= 1.0
+            })
+            .collect();
+
+        StructuringElement::new(width, height, mask)
+    }
+
+    // offsets from the centre pixel covered by the element
+    fn offsets(&self) -> Vec<(i64, i64)> {
+        let centre_x = i64::from(self.width / 2);
+        let centre_y = i64::from(self.height / 2);
+
+        (0..self.width * self.height)
+            .filter(|&i| self.mask[i as usize])
+            .map(|i| (i64::from(i % self.width) - centre_x, i64::from(i / self.width) - centre_y))
+            .collect()
+    }
+}
+
+impl Bmp {
+
+    /// Erodes the image, shrinking bright regions and removing bright specks smaller than the structuring element.
+    ///
+    /// 24-bit and 32-bit images are processed per colour channel. 1-bit and 8-bit images keep their colour table, with
+    /// each pixel taking the darkest colour table entry in its neighbourhood.
+    ///
+    /// # Arguments
+    ///
+    /// * `element` - The neighbourhood examined around each pixel.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::fs::File;
+    /// use std::io;
+    /// use bumpy::bmp::Bmp;
+    /// use bumpy::bmp::morphology::StructuringElement;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut file = File::open("sample.bmp")?;
+    ///     let mut bmp = Bmp::build_from_file(&mut file)?;
+    ///
+    ///     bmp.erode(&StructuringElement::rect(3, 3));
+    ///
+    ///     bmp.write_to_file("test")?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn erode(&mut self, element: &StructuringElement) {
+        self.morph(element, false);
+    }
+
+    /// Dilates the image, growing bright regions and filling dark holes smaller than the structuring element.
+    ///
+    /// # Arguments
+    ///
+    /// * `element` - The neighbourhood examined around each pixel.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::fs::File;
+    /// use std::io;
+    /// use bumpy::bmp::Bmp;
+    /// use bumpy::bmp::morphology::StructuringElement;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut file = File::open("sample.bmp")?;
+    ///     let mut bmp = Bmp::build_from_file(&mut file)?;
+    ///
+    ///     bmp.dilate(&StructuringElement::cross(5, 5));
+    ///
+    ///     bmp.write_to_file("test")?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn dilate(&mut self, element: &StructuringElement) {
+        self.morph(element, true);
+    }
+
+    /// Erodes and then dilates the image, removing bright noise while keeping the shape of larger regions.
+    ///
+    /// # Arguments
+    ///
+    /// * `element` - The neighbourhood examined around each pixel.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::fs::File;
+    /// use std::io;
+    /// use bumpy::bmp::Bmp;
+    /// use bumpy::bmp::morphology::StructuringElement;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut file = File::open("sample.bmp")?;
+    ///     let mut bmp = Bmp::build_from_file(&mut file)?;
+    ///
+    ///     bmp.open(&StructuringElement::ellipse(5, 5));
+    ///
+    ///     bmp.write_to_file("test")?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn open(&mut self, element: &StructuringElement) {
+        self.erode(element);
+        self.dilate(element);
+    }
+
+    /// Dilates and then erodes the image, closing small dark gaps while keeping the shape of larger regions.
+    ///
+    /// # Arguments
+    ///
+    /// * `element` - The neighbourhood examined around each pixel.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::fs::File;
+    /// use std::io;
+    /// use bumpy::bmp::Bmp;
+    /// use bumpy::bmp::morphology::StructuringElement;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut file = File::open("sample.bmp")?;
+    ///     let mut bmp = Bmp::build_from_file(&mut file)?;
+    ///
+    ///     bmp.close(&StructuringElement::ellipse(5, 5));
+    ///
+    ///     bmp.write_to_file("test")?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn close(&mut self, element: &StructuringElement) {
+        self.dilate(element);
+        self.erode(element);
+    }
+
+    /// Keeps only the bright details smaller than the structuring element, by subtracting the opened image.
+    ///
+    /// 8-bit images are expanded to 24-bit, since the difference may use colours missing from the colour table.
+    ///
+    /// # Arguments
+    ///
+    /// * `element` - The neighbourhood examined around each pixel.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::fs::File;
+    /// use std::io;
+    /// use bumpy::bmp::Bmp;
+    /// use bumpy::bmp::morphology::StructuringElement;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut file = File::open("sample.bmp")?;
+    ///     let mut bmp = Bmp::build_from_file(&mut file)?;
+    ///
+    ///     bmp.top_hat(&StructuringElement::rect(9, 9));
+    ///
+    ///     bmp.write_to_file("test")?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn top_hat(&mut self, element: &StructuringElement) {
+        let mut opened = self.clone();
+        opened.open(element);
+
+        self.subtract(&opened);
+    }
+
+    /// Replaces the image with the difference between its dilation and erosion, outlining the edges of regions.
+    ///
+    /// 8-bit images are expanded to 24-bit, since the difference may use colours missing from the colour table.
+    ///
+    /// # Arguments
+    ///
+    /// * `element` - The neighbourhood examined around each pixel.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::fs::File;
+    /// use std::io;
+    /// use bumpy::bmp::Bmp;
+    /// use bumpy::bmp::morphology::StructuringElement;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut file = File::open("sample.bmp")?;
+    ///     let mut bmp = Bmp::build_from_file(&mut file)?;
+    ///
+    ///     bmp.morphological_gradient(&StructuringElement::cross(3, 3));
+    ///
+    ///     bmp.write_to_file("test")?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn morphological_gradient(&mut self, element: &StructuringElement) {
+        let mut eroded = self.clone();
+        eroded.erode(element);
+
+        self.dilate(element);
+        self.subtract(&eroded);
+    }
+
+    // replaces every pixel with the darkest (erosion) or lightest (dilation) pixel under the structuring element
+    fn morph(&mut self, element: &StructuringElement, dilate: bool) {
+        let bits_per_px = self.bits_per_px();
+        let width = self.width();
+        let height = self.height();
+        let offsets = element.offsets();
+
+        // visits the pixels under the element centred on (x, y), clipped to the image
+        let neighbours = |x: u32, y: u32| {
+            offsets.iter().filter_map(move |&(dx, dy)| {
+                let nx = i64::from(x) + dx;
+                let ny = i64::from(y) + dy;
+
+                if nx >= 0 && ny >= 0 && nx < i64::from(width) && ny < i64::from(height) {
+                    Some((nx + i64::from(width) * ny) as usize)
+                }
+                else {
+                    None
+                }
+            })
+        };
+
+        if bits_per_px == 1 || bits_per_px == 8 {
+            let indices = self.indices();
+
+            // order colour table entries by luminance, breaking ties by index
+            let luminance: Vec<u8> = (0..=255)
+                .map(|index| {
+                    let (b, g, r, _) = self.palette_colour(index);
+                    rgb_to_greyscale((b, g, r)).0
+                })
+                .collect();
+            let rank = |index: u8| (luminance[index as usize], index);
+
+            let mut new_indices = Vec::with_capacity(indices.len());
+
+            for y in 0..height {
+                for x in 0..width {
+                    let candidates = neighbours(x, y).map(|i| indices[i]);
+                    let chosen = if dilate { candidates.max_by_key(|&i| rank(i)) } else { candidates.min_by_key(|&i| rank(i)) };
+
+                    new_indices.push(chosen.unwrap_or(indices[(x + width * y) as usize]));
+                }
+            }
+
+            self.set_indices(width, height, bits_per_px, &new_indices);
+        }
+        else if bits_per_px == 24 || bits_per_px == 32 {
+            let pixels = self.pixels();
+
+            let mut new_pixels = Vec::with_capacity(pixels.len());
+
+            for y in 0..height {
+                for x in 0..width {
+                    let (_, _, _, a) = pixels[(x + width * y) as usize];
+                    let mut result = if dilate { (0, 0, 0) } else { (255, 255, 255) };
+
+                    for i in neighbours(x, y) {
+                        let (b, g, r, _) = pixels[i];
+
+                        result = if dilate {
+                            (result.0.max(b), result.1.max(g), result.2.max(r))
+                        }
+                        else {
+                            (result.0.min(b), result.1.min(g), result.2.min(r))
+                        };
+                    }
+
+                    new_pixels.push((result.0, result.1, result.2, a));
+                }
+            }
+
+            self.set_pixels(width, height, bits_per_px, &new_pixels);
+        }
+        else {
+            panic!("Only 32-bit, 24-bit, 8-bit or 1-bit .bmp files are supported");
+        }
+    }
+
+    // subtracts the colours of another image of the same size, clamping at black
+    fn subtract(&mut self, other: &Bmp) {
+        let bits_per_px = self.bits_per_px();
+
+        let difference: Vec<(u8, u8, u8, u8)> = self.pixels()
+            .into_iter()
+            .zip(other.pixels())
+            .map(|((b, g, r, a), (ob, og, or, _))| (b.saturating_sub(ob), g.saturating_sub(og), r.saturating_sub(or), a))
+            .collect();
+
+        if bits_per_px == 1 {
+            // the difference of two binary images is still binary, so map it back onto the colour table
+            for (i, &colour) in difference.iter().enumerate() {
+                self.write_pixel(i as u32 % self.width(), i as u32 / self.width(), colour);
+            }
+        }
+        else {
+            let bits_per_px = if bits_per_px == 32 { 32 } else { 24 };
+
+            self.set_pixels(self.width(), self.height(), bits_per_px, &difference);
+        }
+    }
+}
@@ -0,0 +1,316 @@
+use std::fs::File;
+use std::io::{self, BufWriter, Read, Write};
+use crate::bmp::Bmp;
+use crate::bmp::colour::greyscale_image;
+use crate::bmp::utils::rgb_to_greyscale;
+
+/// The kind of Netpbm image written by `write_to_netpbm_file`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NetpbmFormat {
+    /// A black and white `.pbm` bitmap, read back as a 1-bit image.
+    Bitmap,
+    /// A greyscale `.pgm` graymap, read back as an 8-bit image.
+    Greymap,
+    /// A full colour `.ppm` pixmap, read back as a 24-bit image.
+    Pixmap,
+}
+
+/// How the samples of a Netpbm image are stored.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NetpbmEncoding {
+    /// Decimal numbers separated by whitespace, the P1, P2 and P3 formats.
+    Ascii,
+    /// Packed bytes, the P4, P5 and P6 formats.
+    Binary,
+}
+
+impl Bmp {
+
+    /// Builds a `Bmp` struct from a PBM, PGM or PPM file in any of the P1 to P6 formats.
+    ///
+    /// Bitmaps become 1-bit images with black at index 0 and white at index 1, graymaps become 8-bit greyscale images
+    /// and pixmaps become 24-bit images. Samples with a maximum value other than 255, including 16-bit samples, are
+    /// scaled to 8 bits. Only the first image of a file holding several is read.
+    ///
+    /// # Arguments
+    ///
+    /// * `file` - The file to read from.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` containing the `Bmp` if successful, or an `io::Error` if an error occurred.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::fs::File;
+    /// use std::io;
+    /// use bumpy::bmp::Bmp;
+    /// use bumpy::bmp::netpbm::{NetpbmEncoding, NetpbmFormat};
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut file = File::open("sample4.bmp")?;
+    ///     let bmp = Bmp::build_from_file(&mut file)?;
+    ///
+    ///     bmp.write_to_netpbm_file("test_netpbm_pixmap", NetpbmFormat::Pixmap, NetpbmEncoding::Binary, 255)?;
+    ///
+    ///     let mut file = File::open("test_netpbm_pixmap.ppm")?;
+    ///     let loaded = Bmp::build_from_netpbm_file(&mut file)?;
+    ///     std::fs::remove_file("test_netpbm_pixmap.ppm")?;
+    ///
+    ///     assert_eq!(loaded, bmp);
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn build_from_netpbm_file(file: &mut File) -> io::Result<Self> {
+        let mut bytes = Vec::new();
+        file.read_to_end(&mut bytes)?;
+
+        let invalid = |message: &str| io::Error::new(io::ErrorKind::InvalidData, format!("Invalid Netpbm image: {}", message));
+
+        let magic = bytes.get(..2).ok_or_else(|| invalid("missing magic number"))?;
+        let kind = match magic {
+            [b'P', kind @ b'1'..=b'6'] => kind - b'0',
+            _ => return Err(invalid("unsupported magic number")),
+        };
+
+        let mut position = 2;
+        let number = |position: &mut usize| {
+            next_token(&bytes, position)
+                .and_then(|token| token.parse::<u32>().ok())
+                .ok_or_else(|| invalid("expected a number"))
+        };
+
+        let width = number(&mut position)?;
+        let height = number(&mut position)?;
+
+        if width == 0 || height == 0 {
+            return Err(invalid("image width and height must be greater than zero"));
+        }
+
+        let bitmap = kind == 1 || kind == 4;
+        let max_value = if bitmap { 1 } else { number(&mut position)? };
+
+        if max_value == 0 || max_value > 65535 {
+            return Err(invalid("maximum value must be between 1 and 65535"));
+        }
+
+        let channels = if kind == 3 || kind == 6 { 3 } else { 1 };
+        let count = (width as usize).checked_mul(height as usize)
+            .and_then(|pixels| pixels.checked_mul(channels))
+            .ok_or_else(|| invalid("image too large"))?;
+
+        // every ASCII sample takes at least one character, so the header can't claim more than the file holds
+        let needed = match kind {
+            1..=3 => Some(count),
+            4 => (width.div_ceil(8) as usize).checked_mul(height as usize),
+            _ => count.checked_mul(if max_value < 256 { 1 } else { 2 }),
+        };
+
+        if needed.map_or(true, |needed| needed > bytes.len() - position) {
+            return Err(invalid("truncated pixel data"));
+        }
+
+        let samples: Vec<u32> = match kind {
+            // bitmap digits need no whitespace between them
+            1 => bytes[position..].iter()
+                .filter(|byte| matches!(byte, b'0' | b'1'))
+                .take(count)
+                .map(|&byte| u32::from(byte - b'0'))
+                .collect(),
+            2 | 3 => (0..count)
+                .map(|_| number(&mut position))
+                .collect::<io::Result<Vec<u32>>>()?,
+            _ => {
+                // a single whitespace character separates the header from the binary data
+                let data = bytes.get(position + 1..).unwrap_or_default();
+
+                if kind == 4 {
+                    let row_size = width.div_ceil(8) as usize;
+
+                    (0..count)
+                        .filter_map(|i| {
+                            let (x, y) = (i % width as usize, i / width as usize);
+                            data.get(x / 8 + row_size * y).map(|byte| u32::from(byte >> (7 - x % 8) & 1))
+                        })
+                        .collect()
+                }
+                else if max_value < 256 {
+                    data.iter().take(count).map(|&byte| u32::from(byte)).collect()
+                }
+                else {
+                    data.chunks_exact(2).take(count).map(|pair| u32::from(u16::from_be_bytes([pair[0], pair[1]]))).collect()
+                }
+            }
+        };
+
+        if samples.len() != count {
+            return Err(invalid("truncated pixel data"));
+        }
+
+        // scale every sample to the range of a byte
+        let scale = |value: u32| ((value.min(max_value) * 255 + max_value / 2) / max_value) as u8;
+
+        let bmp = if bitmap {
+            // a set bit is black
+            let indices: Vec<u8> = samples.iter().map(|&value| 1 - value as u8).collect();
+
+            let mut bmp = Bmp::builder().size(width, height).depth(1).build();
+            bmp.set_indices(width, height, 1, &indices);
+            bmp
+        }
+        else if channels == 1 {
+            let values: Vec<u8> = samples.iter().map(|&value| scale(value)).collect();
+
+            greyscale_image(width, height, &values)
+        }
+        else {
+            let pixels: Vec<(u8, u8, u8, u8)> = samples.chunks_exact(3)
+                .map(|rgb| (scale(rgb[2]), scale(rgb[1]), scale(rgb[0]), 255))
+                .collect();
+
+            let mut bmp = Bmp::builder().size(width, height).build();
+            bmp.set_pixels(width, height, 24, &pixels);
+            bmp
+        };
+
+        Ok(bmp)
+    }
+
+    /// Writes the image to a PBM, PGM or PPM file.
+    ///
+    /// Bitmaps mark pixels darker than mid-grey as black, graymaps store the luminance of each pixel and pixmaps store
+    /// its colour. The alpha channel of 32-bit images is dropped.
+    ///
+    /// # Arguments
+    ///
+    /// * `file_name` - The name of the file to write to, without the `.pbm`, `.pgm` or `.ppm` extension.
+    /// * `format` - The kind of image to write.
+    /// * `encoding` - Whether samples are written as ASCII numbers or binary data.
+    /// * `max_value` - The value of a full intensity sample, from 1 to 65535. Values above 255 store 16-bit samples.
+    ///   Ignored for bitmaps.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` containing `()` if successful, or an `io::Error` if an error occurred.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::fs::File;
+    /// use std::io;
+    /// use bumpy::bmp::Bmp;
+    /// use bumpy::bmp::netpbm::{NetpbmEncoding, NetpbmFormat};
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut file = File::open("sample3.bmp")?;
+    ///     let bmp = Bmp::build_from_file(&mut file)?;
+    ///
+    ///     bmp.write_to_netpbm_file("test_netpbm_greymap", NetpbmFormat::Greymap, NetpbmEncoding::Ascii, 65535)?;
+    ///
+    ///     let mut file = File::open("test_netpbm_greymap.pgm")?;
+    ///     let loaded = Bmp::build_from_netpbm_file(&mut file)?;
+    ///     std::fs::remove_file("test_netpbm_greymap.pgm")?;
+    ///
+    ///     assert_eq!(loaded, bmp);
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn write_to_netpbm_file(&self, file_name: &str, format: NetpbmFormat, encoding: NetpbmEncoding, max_value: u16) -> io::Result<()> {
+        self.check_supported_depth();
+
+        if max_value == 0 && format != NetpbmFormat::Bitmap {
+            panic!("Maximum value must be between 1 and 65535");
+        }
+
+        let (extension, magic) = match (format, encoding) {
+            (NetpbmFormat::Bitmap, NetpbmEncoding::Ascii) => ("pbm", "P1"),
+            (NetpbmFormat::Greymap, NetpbmEncoding::Ascii) => ("pgm", "P2"),
+            (NetpbmFormat::Pixmap, NetpbmEncoding::Ascii) => ("ppm", "P3"),
+            (NetpbmFormat::Bitmap, NetpbmEncoding::Binary) => ("pbm", "P4"),
+            (NetpbmFormat::Greymap, NetpbmEncoding::Binary) => ("pgm", "P5"),
+            (NetpbmFormat::Pixmap, NetpbmEncoding::Binary) => ("ppm", "P6"),
+        };
+
+        let width = self.width();
+        let pixels = self.pixels();
+
+        let samples: Vec<u16> = match format {
+            // a set bit is black
+            NetpbmFormat::Bitmap => pixels.iter().map(|&(b, g, r, _)| u16::from(rgb_to_greyscale((b, g, r)).0 < 128)).collect(),
+            NetpbmFormat::Greymap => pixels.iter().map(|&(b, g, r, _)| rgb_to_greyscale((b, g, r)).0).map(u16::from).collect(),
+            NetpbmFormat::Pixmap => pixels.iter().flat_map(|&(b, g, r, _)| [r, g, b]).map(u16::from).collect(),
+        };
+
+        // scale every byte sample to the chosen maximum value
+        let samples: Vec<u16> = if format == NetpbmFormat::Bitmap {
+            samples
+        }
+        else {
+            samples.into_iter().map(|value| ((u32::from(value) * u32::from(max_value) + 127) / 255) as u16).collect()
+        };
+
+        let mut file = BufWriter::new(File::create(format!("{}.{}", file_name, extension))?);
+
+        write!(file, "{}\n{} {}\n", magic, width, self.height())?;
+
+        if format != NetpbmFormat::Bitmap {
+            writeln!(file, "{}", max_value)?;
+        }
+
+        let per_row = if format == NetpbmFormat::Pixmap { 3 * width } else { width } as usize;
+
+        match encoding {
+            NetpbmEncoding::Ascii => {
+                for row in samples.chunks(per_row) {
+                    let line: Vec<String> = row.iter().map(u16::to_string).collect();
+                    writeln!(file, "{}", line.join(" "))?;
+                }
+            }
+            NetpbmEncoding::Binary if format == NetpbmFormat::Bitmap => {
+                // rows are packed eight pixels to a byte, leftmost in the highest bit
+                for row in samples.chunks(per_row) {
+                    let bytes: Vec<u8> = row.chunks(8)
+                        .map(|bits| bits.iter().enumerate().fold(0, |byte, (i, &bit)| byte | (bit as u8) << (7 - i)))
+                        .collect();
+                    file.write_all(&bytes)?;
+                }
+            }
+            NetpbmEncoding::Binary if max_value < 256 => {
+                let bytes: Vec<u8> = samples.iter().map(|&value| value as u8).collect();
+                file.write_all(&bytes)?;
+            }
+            NetpbmEncoding::Binary => {
+                let bytes: Vec<u8> = samples.iter().flat_map(|&value| value.to_be_bytes()).collect();
+                file.write_all(&bytes)?;
+            }
+        }
+
+        file.flush()
+    }
+}
+
+// reads the next whitespace separated header token, skipping comments that run from '#' to the end of the line
+fn next_token<'a>(bytes: &'a [u8], position: &mut usize) -> Option<&'a str> {
+    loop {
+        match bytes.get(*position)? {
+            byte if byte.is_ascii_whitespace() => *position += 1,
+            b'#' => {
+                while bytes.get(*position).is_some_and(|&byte| byte != b'\n' && byte != b'\r') {
+                    *position += 1;
+                }
+            }
+            _ => break,
+        }
+    }
+
+    let start = *position;
+
+    while bytes.get(*position).is_some_and(|byte| !byte.is_ascii_whitespace() && *byte != b'#') {
+        *position += 1;
+    }
+
+    std::str::from_utf8(&bytes[start..*position]).ok()
+}
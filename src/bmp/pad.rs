@@ -0,0 +1,233 @@
+use crate::bmp::Bmp;
+
+/// How pixels outside the original image are filled in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EdgeMode {
+    /// Fills with a solid `(red, green, blue)` colour.
+    Colour(u8, u8, u8),
+    /// Repeats the nearest edge pixel.
+    Replicate,
+    /// Reflects the image about its edges.
+    Mirror,
+    /// Tiles the image, wrapping around to the opposite edge.
+    Wrap,
+}
+
+/// Where the original image is placed on an enlarged canvas.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Anchor {
+    TopLeft,
+    Top,
+    TopRight,
+    Left,
+    Centre,
+    Right,
+    BottomLeft,
+    Bottom,
+    BottomRight,
+}
+
+impl Anchor {
+    // how many halves of the extra space go before the image horizontally and vertically
+    fn weights(&self) -> (u32, u32) {
+        match self {
+            Anchor::TopLeft => (0, 0),
+            Anchor::Top => (1, 0),
+            Anchor::TopRight => (2, 0),
+            Anchor::Left => (0, 1),
+            Anchor::Centre => (1, 1),
+            Anchor::Right => (2, 1),
+            Anchor::BottomLeft => (0, 2),
+            Anchor::Bottom => (1, 2),
+            Anchor::BottomRight => (2, 2),
+        }
+    }
+}
+
+impl Bmp {
+
+    /// Expands the canvas by the given number of pixels on each side.
+    ///
+    /// 1-bit and 8-bit images stay palettised. A solid colour is added to the colour table if it is not already present and
+    /// there is room, otherwise the closest existing entry is used.
+    ///
+    /// # Arguments
+    ///
+    /// * `top` - The number of rows to add above the image.
+    /// * `bottom` - The number of rows to add below the image.
+    /// * `left` - The number of columns to add to the left of the image.
+    /// * `right` - The number of columns to add to the right of the image.
+    /// * `mode` - How the new pixels are filled in.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::fs::File;
+    /// use std::io;
+    /// use bumpy::bmp::Bmp;
+    /// use bumpy::bmp::pad::EdgeMode;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut file = File::open("sample.bmp")?;
+    ///     let mut bmp = Bmp::build_from_file(&mut file)?;
+    ///
+    ///     bmp.extend(10, 20, 30, 40, EdgeMode::Mirror);
+    ///
+    ///     bmp.write_to_file("test")?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn extend(&mut self, top: u32, bottom: u32, left: u32, right: u32, mode: EdgeMode) {
+        let width = self.width();
+        let height = self.height();
+
+        let new_width = width + left + right;
+        let new_height = height + top + bottom;
+
+        // source coordinate for every destination pixel, or `None` where the fill colour is used
+        let mut sources = Vec::with_capacity((new_width * new_height) as usize);
+
+        for y in 0..new_height {
+            let src_y = edge_coordinate(i64::from(y) - i64::from(top), height, mode);
+
+            for x in 0..new_width {
+                let src_x = edge_coordinate(i64::from(x) - i64::from(left), width, mode);
+
+                sources.push(src_x.zip(src_y).map(|(src_x, src_y)| (src_x + width * src_y) as usize));
+            }
+        }
+
+        let bits_per_px = self.bits_per_px();
+        let fill = match mode {
+            EdgeMode::Colour(r, g, b) => (b, g, r, 255),
+            _ => (0, 0, 0, 255),
+        };
+
+        if bits_per_px == 1 || bits_per_px == 8 {
+            let indices = self.indices();
+            let fill_index = match mode {
+                EdgeMode::Colour(..) => {
+                    // existing pixels keep the grey they were shown with before the fill colour is added
+                    self.fill_colour_table();
+                    self.colour_index(fill)
+                }
+                _ => 0,
+            };
+
+            let new_indices: Vec<u8> = sources.iter()
+                .map(|source| source.map_or(fill_index, |i| indices[i]))
+                .collect();
+
+            self.set_indices(new_width, new_height, bits_per_px, &new_indices);
+        }
+        else if bits_per_px == 24 || bits_per_px == 32 {
+            let pixels = self.pixels();
+
+            let new_pixels: Vec<(u8, u8, u8, u8)> = sources.iter()
+                .map(|source| source.map_or(fill, |i| pixels[i]))
+                .collect();
+
+            self.set_pixels(new_width, new_height, bits_per_px, &new_pixels);
+        }
+        else {
+            panic!("Only 32-bit, 24-bit, 8-bit or 1-bit .bmp files are supported");
+        }
+    }
+
+    /// Expands the canvas to the given size, placing the original image according to `anchor`.
+    ///
+    /// # Arguments
+    ///
+    /// * `width` - The width of the new canvas in pixels. Must not be smaller than the current width.
+    /// * `height` - The height of the new canvas in pixels. Must not be smaller than the current height.
+    /// * `anchor` - Where the original image sits on the new canvas.
+    /// * `mode` - How the new pixels are filled in.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::fs::File;
+    /// use std::io;
+    /// use bumpy::bmp::Bmp;
+    /// use bumpy::bmp::pad::{Anchor, EdgeMode};
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut file = File::open("sample.bmp")?;
+    ///     let mut bmp = Bmp::build_from_file(&mut file)?;
+    ///
+    ///     bmp.pad_to(640, 640, Anchor::Centre, EdgeMode::Colour(0, 0, 0));
+    ///
+    ///     bmp.write_to_file("test")?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn pad_to(&mut self, width: u32, height: u32, anchor: Anchor, mode: EdgeMode) {
+        let curr_width = self.width();
+        let curr_height = self.height();
+
+        if width < curr_width || height < curr_height {
+            panic!("Cannot pad an image to a smaller size");
+        }
+
+        let (hor_weight, vert_weight) = anchor.weights();
+
+        let left = (width - curr_width) * hor_weight / 2;
+        let top = (height - curr_height) * vert_weight / 2;
+        let right = width - curr_width - left;
+        let bottom = height - curr_height - top;
+
+        self.extend(top, bottom, left, right, mode);
+    }
+
+    /// Adds a border of equal thickness around the image.
+    ///
+    /// # Arguments
+    ///
+    /// * `size` - The thickness of the border in pixels.
+    /// * `mode` - How the border pixels are filled in.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::fs::File;
+    /// use std::io;
+    /// use bumpy::bmp::Bmp;
+    /// use bumpy::bmp::pad::EdgeMode;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut file = File::open("sample2.bmp")?;
+    ///     let mut bmp = Bmp::build_from_file(&mut file)?;
+    ///
+    ///     bmp.border(2, EdgeMode::Colour(255, 255, 255));
+    ///
+    ///     bmp.write_to_file("test")?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn border(&mut self, size: u32, mode: EdgeMode) {
+        self.extend(size, size, size, size, mode);
+    }
+}
+
+// maps a possibly out of range coordinate back into 0..len, or `None` if it should take the fill colour
+pub(crate) fn edge_coordinate(i: i64, len: u32, mode: EdgeMode) -> Option<u32> {
+    let len = i64::from(len);
+
+    if (0..len).contains(&i) {
+        return Some(i as u32);
+    }
+
+    match mode {
+        EdgeMode::Colour(..) => None,
+        EdgeMode::Replicate => Some(i.clamp(0, len - 1) as u32),
+        EdgeMode::Wrap => Some(i.rem_euclid(len) as u32),
+        EdgeMode::Mirror => {
+            // reflect including the edge pixel, so the pattern repeats every 2 * len
+            let m = i.rem_euclid(2 * len);
+            Some(if m < len { m } else { 2 * len - 1 - m } as u32)
+        }
+    }
+}
@@ -0,0 +1,377 @@
+use crate::bmp::Bmp;
+use crate::bmp::utils::rgb_to_greyscale;
+
+/// The order `sort_palette` arranges colour table entries in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PaletteOrder {
+    /// From darkest to lightest.
+    Luminance,
+    /// From the most used to the least used entry.
+    Usage,
+}
+
+impl Bmp {
+
+    /// Counts how many pixels use each colour table entry of a 1-bit or 8-bit image.
+    ///
+    /// # Returns
+    ///
+    /// Returns one count per colour table entry, in index order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::fs::File;
+    /// use std::io;
+    /// use bumpy::bmp::Bmp;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut file = File::open("sample2.bmp")?;
+    ///     let bmp = Bmp::build_from_file(&mut file)?;
+    ///
+    ///     let usage = bmp.palette_usage();
+    ///
+    ///     assert_eq!(usage.len(), bmp.colour_table.len());
+    ///     assert_eq!(usage.iter().sum::<u32>(), 16);
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn palette_usage(&self) -> Vec<u32> {
+        self.check_indexed();
+
+        let mut usage = vec![0; self.colour_table.len()];
+
+        for index in self.indices() {
+            if let Some(count) = usage.get_mut(index as usize) {
+                *count += 1;
+            }
+        }
+
+        usage
+    }
+
+    /// Adds a colour to the colour table of a 1-bit or 8-bit image.
+    ///
+    /// # Arguments
+    ///
+    /// * `colour` - The colour as a `(red, green, blue)` tuple.
+    ///
+    /// # Returns
+    ///
+    /// Returns the index of the new entry, or of the existing entry if the colour is already in the table.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bumpy::bmp::Bmp;
+    ///
+    /// let mut bmp = Bmp::builder().size(4, 4).depth(8).fill((0, 0, 0)).build();
+    ///
+    /// let red = bmp.add_palette_colour((255, 0, 0));
+    ///
+    /// assert_eq!(red, 1);
+    /// assert_eq!(bmp.add_palette_colour((255, 0, 0)), red);
+    /// ```
+    pub fn add_palette_colour(&mut self, colour: (u8, u8, u8)) -> u8 {
+        self.check_indexed();
+        self.fill_colour_table();
+
+        if let Some(index) = self.colour_table.find(colour) {
+            return index;
+        }
+
+        if self.colour_table.len() >= 1 << self.bits_per_px() {
+            panic!("Colour table is full");
+        }
+
+        let index = self.colour_table.push(colour);
+        self.update_sizes();
+
+        index
+    }
+
+    /// Removes an entry from the colour table of a 1-bit or 8-bit image.
+    ///
+    /// Pixels using the entry are changed to the closest remaining colour, and the entries after it move down by one.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - The index of the entry to remove.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::fs::File;
+    /// use std::io;
+    /// use bumpy::bmp::Bmp;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut file = File::open("sample2.bmp")?;
+    ///     let mut bmp = Bmp::build_from_file(&mut file)?;
+    ///
+    ///     let colours = bmp.colour_table.len();
+    ///     bmp.remove_palette_colour(0);
+    ///
+    ///     assert_eq!(bmp.colour_table.len(), colours - 1);
+    ///     bmp.write_to_file("test")?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn remove_palette_colour(&mut self, index: u8) {
+        self.check_indexed();
+        self.fill_colour_table();
+
+        let len = self.colour_table.len();
+
+        if index as usize >= len {
+            panic!("Colour table index out of range");
+        }
+
+        if len == 1 {
+            panic!("Cannot remove the only colour table entry");
+        }
+
+        let removed = self.colour_table.get(index).unwrap();
+        let mut table = self.colour_table.data.clone();
+        table.remove(index as usize);
+
+        let mut map: Vec<u8> = (0..len).map(|i| if i > index as usize { (i - 1) as u8 } else { i as u8 }).collect();
+        self.colour_table.data = table;
+        map[index as usize] = self.colour_table.nearest(removed).unwrap();
+
+        self.remap_indices(&map);
+    }
+
+    /// Rearranges the colour table of a 1-bit or 8-bit image, updating the pixels so the image looks the same.
+    ///
+    /// # Arguments
+    ///
+    /// * `order` - The current index of each entry, listed in its new position. Must contain every index exactly once.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bumpy::bmp::Bmp;
+    /// use bumpy::bmp::bmp_colour_table::BmpColourTable;
+    ///
+    /// let palette = BmpColourTable::from_rgb(&[(255, 0, 0), (0, 255, 0), (0, 0, 255)]);
+    /// let mut bmp = Bmp::builder().size(4, 4).depth(8).palette(palette).fill((0, 0, 255)).build();
+    ///
+    /// bmp.reorder_palette(&[2, 0, 1]);
+    ///
+    /// assert_eq!(bmp.colour_table.to_rgb(), vec![(0, 0, 255), (255, 0, 0), (0, 255, 0)]);
+    /// assert_eq!(bmp.palette_usage(), vec![16, 0, 0]);
+    /// ```
+    pub fn reorder_palette(&mut self, order: &[u8]) {
+        self.check_indexed();
+        self.fill_colour_table();
+
+        let len = self.colour_table.len();
+        let mut map = vec![None; len];
+
+        if order.len() != len {
+            panic!("Palette order must list every colour table entry exactly once");
+        }
+
+        for (new_index, &old_index) in order.iter().enumerate() {
+            match map.get_mut(old_index as usize) {
+                Some(entry @ None) => *entry = Some(new_index as u8),
+                _ => panic!("Palette order must list every colour table entry exactly once"),
+            }
+        }
+
+        self.colour_table.data = order.iter().map(|&index| self.colour_table.data[index as usize]).collect();
+
+        let map: Vec<u8> = map.into_iter().flatten().collect();
+        self.remap_indices(&map);
+    }
+
+    /// Sorts the colour table of a 1-bit or 8-bit image, updating the pixels so the image looks the same.
+    ///
+    /// Entries that compare equal keep their relative order.
+    ///
+    /// # Arguments
+    ///
+    /// * `order` - How the entries are sorted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::fs::File;
+    /// use std::io;
+    /// use bumpy::bmp::Bmp;
+    /// use bumpy::bmp::palette::PaletteOrder;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut file = File::open("sample2.bmp")?;
+    ///     let mut bmp = Bmp::build_from_file(&mut file)?;
+    ///
+    ///     bmp.sort_palette(PaletteOrder::Usage);
+    ///
+    ///     let usage = bmp.palette_usage();
+    ///     assert!(usage.windows(2).all(|pair| pair[0] >= pair[1]));
+    ///
+    ///     bmp.write_to_file("test")?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn sort_palette(&mut self, order: PaletteOrder) {
+        self.check_indexed();
+        self.fill_colour_table();
+
+        let mut indices: Vec<u8> = (0..self.colour_table.len()).map(|index| index as u8).collect();
+
+        match order {
+            PaletteOrder::Luminance => {
+                let data = &self.colour_table.data;
+                indices.sort_by_key(|&index| {
+                    let (b, g, r, _) = data[index as usize];
+                    rgb_to_greyscale((b, g, r)).0
+                });
+            }
+            PaletteOrder::Usage => {
+                let usage = self.palette_usage();
+                indices.sort_by_key(|&index| std::cmp::Reverse(usage[index as usize]));
+            }
+        }
+
+        self.reorder_palette(&indices);
+    }
+
+    /// Removes the colour table entries of a 1-bit or 8-bit image that no pixel uses.
+    ///
+    /// The remaining entries keep their relative order.
+    ///
+    /// # Returns
+    ///
+    /// Returns the number of entries removed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::fs::File;
+    /// use std::io;
+    /// use bumpy::bmp::Bmp;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut file = File::open("sample2.bmp")?;
+    ///     let mut bmp = Bmp::build_from_file(&mut file)?;
+    ///
+    ///     let before = bmp.clone();
+    ///     bmp.remove_unused_colours();
+    ///
+    ///     assert!(bmp.palette_usage().iter().all(|&count| count > 0));
+    ///     assert_eq!(bmp, before);
+    ///
+    ///     bmp.write_to_file("test")?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn remove_unused_colours(&mut self) -> usize {
+        self.check_indexed();
+        self.fill_colour_table();
+
+        let usage = self.palette_usage();
+        let mut map = vec![0; usage.len()];
+        let mut table = Vec::new();
+
+        for (index, &count) in usage.iter().enumerate() {
+            if count > 0 {
+                map[index] = table.len() as u8;
+                table.push(self.colour_table.data[index]);
+            }
+        }
+
+        let removed = usage.len() - table.len();
+
+        if removed > 0 {
+            self.colour_table.data = table;
+            self.remap_indices(&map);
+        }
+
+        removed
+    }
+
+    /// Returns the number of colour table entries needed to display the image well.
+    ///
+    /// A stored value of zero means every entry is important.
+    ///
+    /// # Returns
+    ///
+    /// Returns the number of important entries, which come first in the colour table.
+    pub fn important_colours(&self) -> u32 {
+        match u32::from_le_bytes(self.info_header.important_colours) {
+            0 => self.colour_table.len() as u32,
+            count => count,
+        }
+    }
+
+    /// Marks the first entries of the colour table as important, so viewers limited to fewer colours keep those.
+    ///
+    /// # Arguments
+    ///
+    /// * `count` - The number of important entries, or 0 to mark every entry as important.
+    pub fn set_important_colours(&mut self, count: u32) {
+        if count > self.colour_table.len() as u32 {
+            panic!("Cannot mark more colours as important than the colour table holds");
+        }
+
+        self.info_header.important_colours = u32::to_le_bytes(count);
+    }
+
+    /// Compacts the colour table of a 1-bit or 8-bit image.
+    ///
+    /// Unused entries are removed and the rest are sorted from the most to the least used, with the pixels remapped
+    /// to match. Every remaining entry is in use, so all of them are marked as important.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::fs::File;
+    /// use std::io;
+    /// use bumpy::bmp::Bmp;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut file = File::open("sample2.bmp")?;
+    ///     let mut bmp = Bmp::build_from_file(&mut file)?;
+    ///
+    ///     let before = bmp.clone();
+    ///     bmp.compact_palette();
+    ///
+    ///     assert_eq!(bmp, before);
+    ///     assert_eq!(bmp.important_colours() as usize, bmp.colour_table.len());
+    ///     assert!(bmp.colour_table.len() < 256);
+    ///
+    ///     bmp.write_to_file("test_compact_palette")?;
+    ///
+    ///     let mut file = File::open("test_compact_palette.bmp")?;
+    ///     let reloaded = Bmp::build_from_file(&mut file)?;
+    ///     std::fs::remove_file("test_compact_palette.bmp")?;
+    ///
+    ///     assert!(reloaded.identical(&bmp));
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn compact_palette(&mut self) {
+        self.remove_unused_colours();
+        self.sort_palette(PaletteOrder::Usage);
+
+        let count = self.colour_table.len() as u32;
+        self.set_important_colours(count);
+    }
+
+    // rewrites every pixel's index through `map`, after the colour table has been rearranged to match
+    fn remap_indices(&mut self, map: &[u8]) {
+        let indices: Vec<u8> = self.indices()
+            .into_iter()
+            .map(|index| map.get(index as usize).copied().unwrap_or(index))
+            .collect();
+
+        self.set_indices(self.width(), self.height(), self.bits_per_px(), &indices);
+    }
+}
@@ -0,0 +1,312 @@
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, Read, Write};
+use crate::bmp::Bmp;
+use crate::bmp::bmp_colour_table::BmpColourTable;
+
+impl BmpColourTable {
+
+    /// Builds a `BmpColourTable` struct from a JASC-PAL palette, as saved by Paint Shop Pro.
+    ///
+    /// # Arguments
+    ///
+    /// * `file` - The file to read from.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` containing the `BmpColourTable` if successful, or an `io::Error` if an error occurred.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::fs::File;
+    /// use std::io;
+    /// use bumpy::bmp::bmp_colour_table::BmpColourTable;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let palette = BmpColourTable::from_rgb(&[(0, 0, 0), (255, 128, 0), (255, 255, 255)]);
+    ///     palette.write_to_jasc_file("test_jasc_palette")?;
+    ///
+    ///     let mut file = File::open("test_jasc_palette.pal")?;
+    ///     let loaded = BmpColourTable::build_from_jasc_file(&mut file)?;
+    ///
+    ///     assert_eq!(loaded, palette);
+    ///     std::fs::remove_file("test_jasc_palette.pal")?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn build_from_jasc_file(file: &mut File) -> io::Result<Self> {
+        let mut source = String::new();
+        file.read_to_string(&mut source)?;
+
+        let invalid = |message: &str| io::Error::new(io::ErrorKind::InvalidData, format!("Invalid JASC palette: {}", message));
+        let mut lines = source.lines().map(str::trim);
+
+        if lines.next() != Some("JASC-PAL") {
+            return Err(invalid("missing JASC-PAL header"));
+        }
+
+        lines.next().ok_or_else(|| invalid("missing version"))?;
+
+        let count: usize = lines.next()
+            .and_then(|line| line.parse().ok())
+            .ok_or_else(|| invalid("missing colour count"))?;
+
+        let colours = lines.filter(|line| !line.is_empty())
+            .take(count)
+            .map(|line| parse_rgb(line).ok_or_else(|| invalid("expected red, green and blue values")))
+            .collect::<io::Result<Vec<_>>>()?;
+
+        if colours.len() != count {
+            return Err(invalid("fewer colours than declared"));
+        }
+
+        check_length(colours.len())?;
+
+        Ok(BmpColourTable::from_rgb(&colours))
+    }
+
+    /// Builds a `BmpColourTable` struct from a GIMP `.gpl` palette.
+    ///
+    /// Colour names are ignored.
+    ///
+    /// # Arguments
+    ///
+    /// * `file` - The file to read from.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` containing the `BmpColourTable` if successful, or an `io::Error` if an error occurred.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::fs::File;
+    /// use std::io;
+    /// use bumpy::bmp::bmp_colour_table::BmpColourTable;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let palette = BmpColourTable::from_rgb(&[(0, 0, 0), (255, 128, 0), (255, 255, 255)]);
+    ///     palette.write_to_gpl_file("test_gpl_palette", "Sunset")?;
+    ///
+    ///     let mut file = File::open("test_gpl_palette.gpl")?;
+    ///     let loaded = BmpColourTable::build_from_gpl_file(&mut file)?;
+    ///
+    ///     assert_eq!(loaded, palette);
+    ///     std::fs::remove_file("test_gpl_palette.gpl")?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn build_from_gpl_file(file: &mut File) -> io::Result<Self> {
+        let mut source = String::new();
+        file.read_to_string(&mut source)?;
+
+        let invalid = |message: &str| io::Error::new(io::ErrorKind::InvalidData, format!("Invalid GIMP palette: {}", message));
+        let mut lines = source.lines().map(str::trim);
+
+        if lines.next() != Some("GIMP Palette") {
+            return Err(invalid("missing GIMP Palette header"));
+        }
+
+        // skip the name and column count, comments and blank lines
+        let colours = lines.filter(|line| {
+                !line.is_empty() && !line.starts_with('#') && !line.starts_with("Name:") && !line.starts_with("Columns:")
+            })
+            .map(|line| parse_rgb(line).ok_or_else(|| invalid("expected red, green and blue values")))
+            .collect::<io::Result<Vec<_>>>()?;
+
+        check_length(colours.len())?;
+
+        Ok(BmpColourTable::from_rgb(&colours))
+    }
+
+    /// Builds a `BmpColourTable` struct from an Adobe `.act` colour table.
+    ///
+    /// Files of 768 bytes hold 256 colours. Files with 4 extra bytes also store the number of colours in use, and only
+    /// those are kept.
+    ///
+    /// # Arguments
+    ///
+    /// * `file` - The file to read from.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` containing the `BmpColourTable` if successful, or an `io::Error` if an error occurred.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::fs::File;
+    /// use std::io;
+    /// use bumpy::bmp::bmp_colour_table::BmpColourTable;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let palette = BmpColourTable::from_rgb(&[(0, 0, 0), (255, 128, 0), (255, 255, 255)]);
+    ///     palette.write_to_act_file("test_act_palette")?;
+    ///
+    ///     let mut file = File::open("test_act_palette.act")?;
+    ///     let loaded = BmpColourTable::build_from_act_file(&mut file)?;
+    ///
+    ///     assert_eq!(loaded, palette);
+    ///     std::fs::remove_file("test_act_palette.act")?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn build_from_act_file(file: &mut File) -> io::Result<Self> {
+        let mut buffer = Vec::new();
+        file.read_to_end(&mut buffer)?;
+
+        let count = match buffer.len() {
+            768 => 256,
+            772 => usize::from(u16::from_be_bytes([buffer[768], buffer[769]])).min(256),
+            _ => return Err(io::Error::new(io::ErrorKind::InvalidData, "Invalid ACT palette: expected 768 or 772 bytes")),
+        };
+
+        let colours: Vec<(u8, u8, u8)> = buffer[..3 * count]
+            .chunks_exact(3)
+            .map(|chunk| (chunk[0], chunk[1], chunk[2]))
+            .collect();
+
+        Ok(BmpColourTable::from_rgb(&colours))
+    }
+
+    /// Writes the colour table to a JASC-PAL palette, as read by Paint Shop Pro.
+    ///
+    /// # Arguments
+    ///
+    /// * `file_name` - The name of the file to write to, without the `.pal` extension.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` containing `()` if successful, or an `io::Error` if an error occurred.
+    pub fn write_to_jasc_file(&self, file_name: &str) -> io::Result<()> {
+        let mut file = File::create(format!("{}.pal", file_name))?;
+
+        write!(file, "JASC-PAL\r\n0100\r\n{}\r\n", self.len())?;
+
+        for (r, g, b) in self.to_rgb() {
+            write!(file, "{} {} {}\r\n", r, g, b)?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes the colour table to a GIMP `.gpl` palette.
+    ///
+    /// # Arguments
+    ///
+    /// * `file_name` - The name of the file to write to, without the `.gpl` extension.
+    /// * `name` - The palette name shown by GIMP.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` containing `()` if successful, or an `io::Error` if an error occurred.
+    pub fn write_to_gpl_file(&self, file_name: &str, name: &str) -> io::Result<()> {
+        let mut file = File::create(format!("{}.gpl", file_name))?;
+
+        write!(file, "GIMP Palette\nName: {}\nColumns: 16\n#\n", name)?;
+
+        for (index, (r, g, b)) in self.to_rgb().into_iter().enumerate() {
+            writeln!(file, "{:3} {:3} {:3}\tIndex {}", r, g, b, index)?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes the colour table to an Adobe `.act` colour table.
+    ///
+    /// The file is padded to 256 colours with black, followed by the number of colours in use and no transparent
+    /// colour.
+    ///
+    /// # Arguments
+    ///
+    /// * `file_name` - The name of the file to write to, without the `.act` extension.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` containing `()` if successful, or an `io::Error` if an error occurred.
+    pub fn write_to_act_file(&self, file_name: &str) -> io::Result<()> {
+        let mut file = File::create(format!("{}.act", file_name))?;
+        let mut buffer = vec![0; 772];
+
+        for (index, (r, g, b)) in self.to_rgb().into_iter().take(256).enumerate() {
+            buffer[3 * index..3 * index + 3].copy_from_slice(&[r, g, b]);
+        }
+
+        buffer[768..770].copy_from_slice(&u16::to_be_bytes(self.len().min(256) as u16));
+        buffer[770..772].copy_from_slice(&[0xFF, 0xFF]);
+
+        file.write_all(&buffer)
+    }
+}
+
+impl Bmp {
+
+    /// Converts the image to an 8-bit image using the given colour table.
+    ///
+    /// Every pixel takes the closest colour in the table, so images of any supported bit depth can be remapped,
+    /// including 8-bit images onto a palette imported from another program.
+    ///
+    /// # Arguments
+    ///
+    /// * `palette` - The colour table to use, with between 1 and 256 entries.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::fs::File;
+    /// use std::io;
+    /// use bumpy::bmp::Bmp;
+    /// use bumpy::bmp::bmp_colour_table::BmpColourTable;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut file = File::open("sample.bmp")?;
+    ///     let mut bmp = Bmp::build_from_file(&mut file)?;
+    ///
+    ///     let palette = BmpColourTable::from_rgb(&[(0, 0, 0), (128, 128, 128), (255, 255, 255), (200, 60, 40)]);
+    ///     bmp.remap_to_palette(&palette);
+    ///
+    ///     assert_eq!(bmp.colour_table, palette);
+    ///     bmp.write_to_file("test")?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn remap_to_palette(&mut self, palette: &BmpColourTable) {
+        self.check_supported_depth();
+
+        if palette.is_empty() || palette.len() > 256 {
+            panic!("Palette must have between 1 and 256 entries");
+        }
+
+        // most images use far fewer colours than pixels, so look each one up once
+        let mut cache = HashMap::new();
+
+        let indices: Vec<u8> = self.pixels()
+            .into_iter()
+            .map(|(b, g, r, _)| *cache.entry((r, g, b)).or_insert_with(|| palette.nearest((r, g, b)).unwrap()))
+            .collect();
+
+        self.colour_table.data = palette.data.clone();
+        self.set_indices(self.width(), self.height(), 8, &indices);
+    }
+}
+
+// reads the first three whitespace separated numbers of a line as a colour
+fn parse_rgb(line: &str) -> Option<(u8, u8, u8)> {
+    let mut values = line.split_whitespace().map(|value| value.parse::<u8>().ok());
+
+    Some((values.next()??, values.next()??, values.next()??))
+}
+
+// colour tables hold at most 256 entries
+fn check_length(length: usize) -> io::Result<()> {
+    if length > 256 {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "Palette has more than 256 colours"));
+    }
+
+    Ok(())
+}
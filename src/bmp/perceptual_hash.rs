@@ -0,0 +1,184 @@
+use std::f64::consts::PI;
+use crate::bmp::Bmp;
+use crate::bmp::resize::Filter;
+use crate::bmp::utils::rgb_to_greyscale;
+
+/// A 64-bit perceptual hash, where visually similar images have hashes that differ in few bits.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ImageHash(pub u64);
+
+impl ImageHash {
+
+    /// Counts the bits that differ between two hashes of the same kind.
+    ///
+    /// Distances up to around 10 usually mean the images are near-duplicates.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The hash to compare against.
+    ///
+    /// # Returns
+    ///
+    /// Returns the Hamming distance, from 0 for matching hashes to 64.
+    pub fn distance(&self, other: &ImageHash) -> u32 {
+        (self.0 ^ other.0).count_ones()
+    }
+}
+
+impl Bmp {
+
+    /// Computes the average hash (aHash), marking which cells of an 8x8 greyscale thumbnail are brighter than the mean.
+    ///
+    /// Fast, and robust to scaling and small colour changes.
+    ///
+    /// # Returns
+    ///
+    /// Returns an `ImageHash` struct.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::fs::File;
+    /// use std::io;
+    /// use bumpy::bmp::Bmp;
+    /// use bumpy::bmp::resize::Filter;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut file = File::open("sample.bmp")?;
+    ///     let bmp = Bmp::build_from_file(&mut file)?;
+    ///
+    ///     let mut smaller = bmp.clone();
+    ///     smaller.resize(200, 200, Filter::Bilinear);
+    ///
+    ///     assert!(bmp.average_hash().distance(&smaller.average_hash()) <= 4);
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn average_hash(&self) -> ImageHash {
+        let grid = self.luminance_grid(8, 8);
+        let mean = grid.iter().sum::<f64>() / grid.len() as f64;
+
+        pack_bits(grid.iter().map(|&value| value > mean))
+    }
+
+    /// Computes the difference hash (dHash), marking where brightness increases between neighbouring cells of a 9x8
+    /// greyscale thumbnail.
+    ///
+    /// Tracks gradients rather than absolute brightness, so it is robust to brightness and contrast changes.
+    ///
+    /// # Returns
+    ///
+    /// Returns an `ImageHash` struct.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::fs::File;
+    /// use std::io;
+    /// use bumpy::bmp::Bmp;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut file = File::open("sample.bmp")?;
+    ///     let bmp = Bmp::build_from_file(&mut file)?;
+    ///
+    ///     let mut brighter = bmp.clone();
+    ///     brighter.adjust_brightness(20);
+    ///
+    ///     let mut file = File::open("sample4.bmp")?;
+    ///     let other = Bmp::build_from_file(&mut file)?;
+    ///
+    ///     assert!(bmp.difference_hash().distance(&brighter.difference_hash()) <= 4);
+    ///     assert!(bmp.difference_hash().distance(&other.difference_hash()) > 10);
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn difference_hash(&self) -> ImageHash {
+        let grid = self.luminance_grid(9, 8);
+
+        pack_bits((0..8).flat_map(|y| {
+            let row = &grid[9 * y..9 * y + 9];
+            (0..8).map(move |x| row[x] < row[x + 1])
+        }))
+    }
+
+    /// Computes the DCT-based perceptual hash (pHash), marking which of the lowest 8x8 frequencies of a 32x32 greyscale
+    /// thumbnail are above their median.
+    ///
+    /// Slower than the other hashes, but the most robust to blurring, compression artefacts and gamma changes.
+    ///
+    /// # Returns
+    ///
+    /// Returns an `ImageHash` struct.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::fs::File;
+    /// use std::io;
+    /// use bumpy::bmp::Bmp;
+    /// use bumpy::bmp::pad::EdgeMode;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut file = File::open("sample.bmp")?;
+    ///     let bmp = Bmp::build_from_file(&mut file)?;
+    ///
+    ///     let mut blurred = bmp.clone();
+    ///     blurred.gaussian_blur(2.0, EdgeMode::Replicate);
+    ///
+    ///     assert!(bmp.perceptual_hash().distance(&blurred.perceptual_hash()) <= 8);
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn perceptual_hash(&self) -> ImageHash {
+        const SIZE: usize = 32;
+
+        let grid = self.luminance_grid(SIZE as u32, SIZE as u32);
+
+        // cos_table[u * SIZE + x] is the DCT-II basis function for frequency u at sample x
+        let cos_table: Vec<f64> = (0..8 * SIZE)
+            .map(|i| ((2 * (i % SIZE) + 1) as f64 * (i / SIZE) as f64 * PI / (2 * SIZE) as f64).cos())
+            .collect();
+
+        // transform the rows, keeping only the lowest 8 frequencies, and then the columns
+        let rows: Vec<f64> = (0..SIZE)
+            .flat_map(|y| {
+                let grid = &grid;
+                let cos_table = &cos_table;
+                (0..8).map(move |u| (0..SIZE).map(|x| grid[x + SIZE * y] * cos_table[x + SIZE * u]).sum::<f64>())
+            })
+            .collect();
+
+        let coefficients: Vec<f64> = (0..8)
+            .flat_map(|v| {
+                let rows = &rows;
+                let cos_table = &cos_table;
+                (0..8).map(move |u| (0..SIZE).map(|y| rows[u + 8 * y] * cos_table[y + SIZE * v]).sum::<f64>())
+            })
+            .collect();
+
+        let mut sorted = coefficients.clone();
+        sorted.sort_by(f64::total_cmp);
+        let median = (sorted[31] + sorted[32]) / 2.0;
+
+        pack_bits(coefficients.iter().map(|&value| value > median))
+    }
+
+    // luminance of the image shrunk to the given size, top row first
+    fn luminance_grid(&self, width: u32, height: u32) -> Vec<f64> {
+        let mut thumbnail = self.clone();
+        thumbnail.resize(width, height, Filter::Box);
+
+        thumbnail.pixels()
+            .into_iter()
+            .map(|(b, g, r, _)| f64::from(rgb_to_greyscale((b, g, r)).0))
+            .collect()
+    }
+}
+
+// packs 64 flags into a hash, the first flag becoming the most significant bit
+fn pack_bits<I: Iterator<Item = bool>>(bits: I) -> ImageHash {
+    ImageHash(bits.fold(0, |hash, bit| hash << 1 | u64::from(bit)))
+}
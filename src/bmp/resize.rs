@@ -0,0 +1,391 @@
+use std::f64::consts::PI;
+
+use crate::bmp::Bmp;
+use crate::bmp::colour::{linear_to_srgb, srgb_to_linear};
+use crate::bmp::utils::clamp_channel;
+
+/// The resampling filter used when resizing an image.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Filter {
+    /// Picks the closest source pixel without blending, at every bit depth. Keeps 1-bit and 8-bit images palettised.
+    Nearest,
+    /// Linear interpolation between the two closest pixels on each axis.
+    Bilinear,
+    /// Catmull-Rom cubic interpolation over a 4x4 neighbourhood.
+    Bicubic,
+    /// Windowed sinc interpolation over a 6x6 neighbourhood.
+    Lanczos3,
+    /// Area averaging, best suited to downscaling.
+    Box,
+}
+
+impl Filter {
+    // radius of the filter kernel in source pixels, before scaling
+    fn support(&self) -> f64 {
+        match self {
+            Filter::Nearest | Filter::Box => 0.5,
+            Filter::Bilinear => 1.0,
+            Filter::Bicubic => 2.0,
+            Filter::Lanczos3 => 3.0,
+        }
+    }
+
+    // weight of a source pixel at distance `x` from the sample centre
+    fn kernel(&self, x: f64) -> f64 {
+        let x = x.abs();
+
+        match self {
+            Filter::Nearest | Filter::Box => {
+                if x <= 0.5 { 1.0 } else { 0.0 }
+            }
+            Filter::Bilinear => {
+                if x < 1.0 { 1.0 - x } else { 0.0 }
+            }
+            Filter::Bicubic => {
+                // Catmull-Rom spline, a = -0.5
+                if x < 1.0 {
+                    1.5 * x * x * x - 2.5 * x * x + 1.0
+                }
+                else if x < 2.0 {
+                    -0.5 * x * x * x + 2.5 * x * x - 4.0 * x + 2.0
+                }
+                else {
+                    0.0
+                }
+            }
+            Filter::Lanczos3 => {
+                if x == 0.0 {
+                    1.0
+                }
+                else if x < 3.0 {
+                    let px = PI * x;
+                    3.0 * px.sin() * (px / 3.0).sin() / (px * px)
+                }
+                else {
+                    0.0
+                }
+            }
+        }
+    }
+}
+
+impl Bmp {
+
+    /// Resizes the image to the given dimensions using the given resampling filter.
+    ///
+    /// 1-bit and 8-bit images keep their colour table when resized with `Filter::Nearest`; any other filter blends colours
+    /// together, so the image is expanded to 24-bit first.
+    ///
+    /// # Arguments
+    ///
+    /// * `width` - The new width of the image in pixels.
+    /// * `height` - The new height of the image in pixels.
+    /// * `filter` - The resampling filter to use.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::fs::File;
+    /// use std::io;
+    /// use bumpy::bmp::Bmp;
+    /// use bumpy::bmp::resize::Filter;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut file = File::open("sample.bmp")?;
+    ///     let mut bmp = Bmp::build_from_file(&mut file)?;
+    ///
+    ///     bmp.resize(300, 200, Filter::Lanczos3);
+    ///
+    ///     bmp.write_to_file("test")?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// `Filter::Nearest` only ever copies source pixels, so shrinking black and white stripes never produces grey:
+    ///
+    /// ```
+    /// use bumpy::bmp::Bmp;
+    /// use bumpy::bmp::resize::Filter;
+    ///
+    /// let mut bmp = Bmp::builder().size(8, 8).fill((255, 255, 255)).build();
+    ///
+    /// for x in (0..8).step_by(2) {
+    ///     bmp.fill_rect(x, 0, 1, 8, (0, 0, 0));
+    /// }
+    ///
+    /// bmp.resize(3, 3, Filter::Nearest);
+    ///
+    /// let histogram = bmp.histogram();
+    /// assert!(histogram.luminance.iter().enumerate().all(|(value, &count)| count == 0 || value == 0 || value == 255));
+    /// ```
+    pub fn resize(&mut self, width: u32, height: u32, filter: Filter) {
+        self.resize_with(width, height, filter, false);
+    }
+
+    /// Resizes the image like `resize`, but blends colours in linear light rather than on their gamma-encoded values.
+    ///
+    /// Averaging encoded sRGB values darkens fine detail and the edges between bright and dark areas, most visibly
+    /// when shrinking an image. Decoding to linear light first keeps the overall brightness of the original.
+    ///
+    /// # Arguments
+    ///
+    /// * `width` - The new width of the image in pixels.
+    /// * `height` - The new height of the image in pixels.
+    /// * `filter` - The resampling filter to use.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::fs::File;
+    /// use std::io;
+    /// use bumpy::bmp::Bmp;
+    /// use bumpy::bmp::resize::Filter;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut file = File::open("sample5.bmp")?;
+    ///     let mut bmp = Bmp::build_from_file(&mut file)?;
+    ///
+    ///     bmp.resize_linear(190, 327, Filter::Box);
+    ///
+    ///     bmp.write_to_file("test")?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn resize_linear(&mut self, width: u32, height: u32, filter: Filter) {
+        self.resize_with(width, height, filter, true);
+    }
+
+    /// Shrinks the image to fit within the given bounds while preserving its aspect ratio.
+    ///
+    /// Images that already fit are left untouched.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_width` - The maximum width of the thumbnail in pixels.
+    /// * `max_height` - The maximum height of the thumbnail in pixels.
+    /// * `filter` - The resampling filter to use.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::fs::File;
+    /// use std::io;
+    /// use bumpy::bmp::Bmp;
+    /// use bumpy::bmp::resize::Filter;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut file = File::open("sample.bmp")?;
+    ///     let mut bmp = Bmp::build_from_file(&mut file)?;
+    ///
+    ///     bmp.thumbnail(64, 64, Filter::Box);
+    ///
+    ///     bmp.write_to_file("test")?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn thumbnail(&mut self, max_width: u32, max_height: u32, filter: Filter) {
+        if let Some((width, height)) = self.thumbnail_size(max_width, max_height) {
+            self.resize_with(width, height, filter, false);
+        }
+    }
+
+    /// Shrinks the image like `thumbnail`, but blends colours in linear light as `resize_linear` does.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_width` - The largest width the thumbnail may have.
+    /// * `max_height` - The largest height the thumbnail may have.
+    /// * `filter` - The resampling filter to use.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::fs::File;
+    /// use std::io;
+    /// use bumpy::bmp::Bmp;
+    /// use bumpy::bmp::resize::Filter;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut file = File::open("sample5.bmp")?;
+    ///     let mut bmp = Bmp::build_from_file(&mut file)?;
+    ///
+    ///     bmp.thumbnail_linear(128, 128, Filter::Lanczos3);
+    ///
+    ///     bmp.write_to_file("test")?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn thumbnail_linear(&mut self, max_width: u32, max_height: u32, filter: Filter) {
+        if let Some((width, height)) = self.thumbnail_size(max_width, max_height) {
+            self.resize_with(width, height, filter, true);
+        }
+    }
+
+    // the size of a thumbnail fitting within the given bounds, or `None` if the image already fits
+    fn thumbnail_size(&self, max_width: u32, max_height: u32) -> Option<(u32, u32)> {
+        let width = self.width();
+        let height = self.height();
+
+        if width <= max_width && height <= max_height {
+            return None;
+        }
+
+        let scale = f64::min(f64::from(max_width) / f64::from(width), f64::from(max_height) / f64::from(height));
+
+        let new_width = ((f64::from(width) * scale).round() as u32).max(1);
+        let new_height = ((f64::from(height) * scale).round() as u32).max(1);
+
+        Some((new_width, new_height))
+    }
+
+    // resizes the image, blending colours in linear light when `linear` is set
+    fn resize_with(&mut self, width: u32, height: u32, filter: Filter, linear: bool) {
+        if width == 0 || height == 0 {
+            panic!("Cannot resize an image to zero width or height");
+        }
+
+        let bits_per_px = self.bits_per_px();
+
+        if filter == Filter::Nearest && (bits_per_px == 1 || bits_per_px == 8 || bits_per_px == 24 || bits_per_px == 32) {
+            self.resize_nearest(width, height);
+        }
+        else if bits_per_px == 1 || bits_per_px == 8 || bits_per_px == 24 || bits_per_px == 32 {
+            self.resize_filtered(width, height, filter, linear);
+        }
+        else {
+            panic!("Only 32-bit, 24-bit, 8-bit or 1-bit .bmp files are supported");
+        }
+    }
+
+    // nearest neighbour resize, copying colour table indices of 1-bit and 8-bit images and pixels of the others
+    fn resize_nearest(&mut self, width: u32, height: u32) {
+        let src_width = self.width();
+        let src_height = self.height();
+        let bits_per_px = self.bits_per_px();
+
+        let sources: Vec<(u32, u32)> = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (nearest_source(x, width, src_width), nearest_source(y, height, src_height))))
+            .collect();
+
+        if bits_per_px == 1 || bits_per_px == 8 {
+            let new_indices: Vec<u8> = sources.iter().map(|&(x, y)| self.read_index(x, y)).collect();
+
+            self.set_indices(width, height, bits_per_px, &new_indices);
+        }
+        else {
+            let new_pixels: Vec<(u8, u8, u8, u8)> = sources.iter().map(|&(x, y)| self.read_pixel(x, y)).collect();
+
+            self.set_pixels(width, height, bits_per_px, &new_pixels);
+        }
+    }
+
+    // separable resize of the decoded pixels, storing the result at 24 or 32 bits per pixel
+    fn resize_filtered(&mut self, width: u32, height: u32, filter: Filter, linear: bool) {
+        let src_width = self.width() as usize;
+        let src_height = self.height() as usize;
+
+        // colour channels are resampled from 0.0 to 255.0, either as stored or converted to linear light
+        let decode = |value: u8| if linear { srgb_to_linear(value) * 255.0 } else { f64::from(value) };
+        let encode = |value: f64| {
+            if linear {
+                linear_to_srgb(value / 255.0)
+            }
+            else {
+                clamp_channel(value)
+            }
+        };
+
+        let pixels: Vec<[f64; 4]> = self.pixels()
+            .into_iter()
+            .map(|(b, g, r, a)| [decode(b), decode(g), decode(r), f64::from(a)])
+            .collect();
+
+        // resample rows first, then columns
+        let horizontal = resample_axis(&pixels, src_width, src_height, width as usize, filter, true);
+        let resized = resample_axis(&horizontal, width as usize, src_height, height as usize, filter, false);
+
+        let new_pixels: Vec<(u8, u8, u8, u8)> = resized
+            .into_iter()
+            .map(|[b, g, r, a]| (encode(b), encode(g), encode(r), clamp_channel(a)))
+            .collect();
+
+        let bits_per_px = if self.bits_per_px() == 32 { 32 } else { 24 };
+
+        self.set_pixels(width, height, bits_per_px, &new_pixels);
+    }
+}
+
+// maps a destination coordinate to the closest source coordinate
+fn nearest_source(dst: u32, dst_len: u32, src_len: u32) -> u32 {
+    let src = ((f64::from(dst) + 0.5) * f64::from(src_len) / f64::from(dst_len)) as u32;
+
+    src.min(src_len - 1)
+}
+
+// resamples `pixels` (width x height, row major) along one axis to `new_len` samples
+fn resample_axis(pixels: &[[f64; 4]], width: usize, height: usize, new_len: usize, filter: Filter, horizontal: bool) -> Vec<[f64; 4]> {
+    let src_len = if horizontal { width } else { height };
+    let lines = if horizontal { height } else { width };
+    let weights = filter_weights(src_len, new_len, filter);
+
+    let (out_width, out_height) = if horizontal { (new_len, height) } else { (width, new_len) };
+    let mut out = vec![[0.0; 4]; out_width * out_height];
+
+    for line in 0..lines {
+        for (i, (start, line_weights)) in weights.iter().enumerate() {
+            let mut sum = [0.0; 4];
+
+            for (k, weight) in line_weights.iter().enumerate() {
+                let src = if horizontal { pixels[line * width + start + k] } else { pixels[(start + k) * width + line] };
+
+                for c in 0..4 {
+                    sum[c] += src[c] * weight;
+                }
+            }
+
+            let dst = if horizontal { line * out_width + i } else { i * out_width + line };
+            out[dst] = sum;
+        }
+    }
+
+    out
+}
+
+// computes, for each destination sample, the first contributing source sample and the normalised weights
+fn filter_weights(src_len: usize, dst_len: usize, filter: Filter) -> Vec<(usize, Vec<f64>)> {
+    let scale = src_len as f64 / dst_len as f64;
+
+    // widen the kernel when downscaling so every source pixel contributes
+    let filter_scale = scale.max(1.0);
+    let support = filter.support() * filter_scale;
+
+    (0..dst_len).map(|i| {
+        let centre = (i as f64 + 0.5) * scale;
+
+        let start = ((centre - support).floor().max(0.0) as usize).min(src_len - 1);
+        let end = ((centre + support).ceil() as usize).clamp(start + 1, src_len);
+
+        let mut weights: Vec<f64> = (start..end)
+            .map(|j| filter.kernel((j as f64 + 0.5 - centre) / filter_scale))
+            .collect();
+
+        let total: f64 = weights.iter().sum();
+
+        if total == 0.0 {
+            // the kernel fell between samples, use the closest one instead
+            let closest = (centre as usize).clamp(start, end - 1);
+            weights = (start..end).map(|j| if j == closest { 1.0 } else { 0.0 }).collect();
+        }
+        else {
+            for weight in weights.iter_mut() {
+                *weight /= total;
+            }
+        }
+
+        (start, weights)
+    }).collect()
+}
@@ -0,0 +1,126 @@
+use crate::bmp::Bmp;
+use crate::bmp::resize::Filter;
+
+// metres per inch
+const METRES_PER_INCH: f64 = 0.0254;
+
+impl Bmp {
+
+    /// Returns the physical resolution of the image in dots per inch.
+    ///
+    /// # Returns
+    ///
+    /// Returns the horizontal and vertical resolution, or `None` if the file does not record one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::fs::File;
+    /// use std::io;
+    /// use bumpy::bmp::Bmp;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut file = File::open("sample6.bmp")?;
+    ///     let bmp = Bmp::build_from_file(&mut file)?;
+    ///
+    ///     let (x_dpi, y_dpi) = bmp.dpi().unwrap();
+    ///
+    ///     assert_eq!((x_dpi.round(), y_dpi.round()), (72.0, 72.0));
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn dpi(&self) -> Option<(f64, f64)> {
+        let x_per_m = u32::from_le_bytes(self.info_header.x_per_m);
+        let y_per_m = u32::from_le_bytes(self.info_header.y_per_m);
+
+        if x_per_m == 0 || y_per_m == 0 {
+            return None;
+        }
+
+        Some((f64::from(x_per_m) * METRES_PER_INCH, f64::from(y_per_m) * METRES_PER_INCH))
+    }
+
+    /// Sets the physical resolution of the image, without changing its pixels.
+    ///
+    /// The resolution is stored in whole pixels per metre, so reading it back may differ slightly from the value set.
+    ///
+    /// # Arguments
+    ///
+    /// * `x_dpi` - The horizontal resolution in dots per inch.
+    /// * `y_dpi` - The vertical resolution in dots per inch.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bumpy::bmp::Bmp;
+    ///
+    /// let mut bmp = Bmp::new(100, 100);
+    ///
+    /// bmp.set_dpi(300.0, 300.0);
+    ///
+    /// assert_eq!(bmp.info_header.x_per_m, u32::to_le_bytes(11811));
+    /// ```
+    pub fn set_dpi(&mut self, x_dpi: f64, y_dpi: f64) {
+        if x_dpi.is_nan() || y_dpi.is_nan() || x_dpi <= 0.0 || y_dpi <= 0.0 {
+            panic!("Resolution must be greater than zero");
+        }
+
+        self.info_header.x_per_m = u32::to_le_bytes((x_dpi / METRES_PER_INCH).round() as u32);
+        self.info_header.y_per_m = u32::to_le_bytes((y_dpi / METRES_PER_INCH).round() as u32);
+    }
+
+    /// Resamples the image to a new resolution, keeping its physical size.
+    ///
+    /// A 4 inch wide image at 72 DPI becomes 1200 pixels wide at 300 DPI. Both directions are resampled to the same
+    /// resolution, so images with different horizontal and vertical resolutions come out with square pixels.
+    ///
+    /// # Arguments
+    ///
+    /// * `dpi` - The new resolution in dots per inch.
+    /// * `filter` - The resampling filter to use.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::fs::File;
+    /// use std::io;
+    /// use bumpy::bmp::Bmp;
+    /// use bumpy::bmp::resize::Filter;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut file = File::open("sample6.bmp")?;
+    ///     let mut bmp = Bmp::build_from_file(&mut file)?;
+    ///
+    ///     // 480 pixels at 72 DPI
+    ///     bmp.resample_to_dpi(36.0, Filter::Lanczos3);
+    ///
+    ///     assert_eq!(u32::from_le_bytes(bmp.info_header.width), 240);
+    ///     bmp.write_to_file("test")?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn resample_to_dpi(&mut self, dpi: f64, filter: Filter) {
+        let (x_dpi, y_dpi) = match self.dpi() {
+            Some(resolution) => resolution,
+            None => panic!("Image has no resolution to resample from"),
+        };
+
+        if dpi.is_nan() || dpi <= 0.0 {
+            panic!("Resolution must be greater than zero");
+        }
+
+        let width = (f64::from(self.width()) * dpi / x_dpi).round().max(1.0) as u32;
+        let height = (f64::from(self.height()) * dpi / y_dpi).round().max(1.0) as u32;
+
+        self.resize(width, height, filter);
+        self.set_dpi(dpi, dpi);
+    }
+
+    // gives a new image the resolution of the image it was made from
+    pub(crate) fn copy_resolution(&mut self, other: &Bmp) {
+        self.info_header.x_per_m = other.info_header.x_per_m;
+        self.info_header.y_per_m = other.info_header.y_per_m;
+    }
+}
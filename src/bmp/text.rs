@@ -0,0 +1,542 @@
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, Read};
+use crate::bmp::Bmp;
+
+/// A fixed-size bitmap font, with every glyph occupying a cell of the same width and height.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Font {
+    pub width: u32,
+    pub height: u32,
+    glyphs: HashMap<char, Vec<bool>>,
+}
+
+/// How text is drawn by `Bmp::draw_text`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TextStyle {
+    /// The colour of the glyphs as a `(red, green, blue)` tuple.
+    pub colour: (u8, u8, u8),
+    /// The whole number of image pixels each font pixel is scaled up to.
+    pub scale: u32,
+    /// The colour the cell behind each character is filled with, if any.
+    pub background: Option<(u8, u8, u8)>,
+}
+
+impl Default for TextStyle {
+    fn default() -> Self {
+        TextStyle {
+            colour: (255, 255, 255),
+            scale: 1,
+            background: None
+        }
+    }
+}
+
+impl Font {
+
+    /// Creates the embedded 8x8 font, covering printable ASCII.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Font` struct.
+    pub fn builtin() -> Self {
+        let glyphs = BUILTIN_GLYPHS.iter()
+            .enumerate()
+            .map(|(i, rows)| {
+                // the least significant bit of each row is the leftmost pixel
+                let bitmap = rows.iter().flat_map(|&row| (0..8).map(move |bit| row >> bit & 1 == 1)).collect();
+                (char::from(b' ' + i as u8), bitmap)
+            })
+            .collect();
+
+        Font {
+            width: 8,
+            height: 8,
+            glyphs
+        }
+    }
+
+    /// Loads a PC Screen Font (version 1 or 2) from a file.
+    ///
+    /// Characters are mapped through the font's Unicode table when it has one, and otherwise glyph `n` is used for
+    /// the character with code point `n`.
+    ///
+    /// # Arguments
+    ///
+    /// * `file` - The .psf file to read.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Font` struct, or an error if the file could not be read or is not a valid PSF font.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::fs::File;
+    /// use std::io;
+    /// use bumpy::bmp::text::Font;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut file = File::open("sample.psf")?;
+    ///     let font = Font::build_from_psf_file(&mut file)?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn build_from_psf_file(file: &mut File) -> io::Result<Self> {
+        let mut data = Vec::new();
+        file.read_to_end(&mut data)?;
+
+        let truncated = || io::Error::new(io::ErrorKind::InvalidData, "Truncated PSF font");
+        let read_u32 = |offset: usize| {
+            data.get(offset..offset + 4)
+                .map(|bytes| u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+                .ok_or_else(truncated)
+        };
+
+        let (width, height, count, glyph_start, glyph_size, table) = if data.starts_with(&[0x36, 0x04]) {
+            let mode = *data.get(2).ok_or_else(truncated)?;
+            let height = *data.get(3).ok_or_else(truncated)? as u32;
+            let count = if mode & 0x01 != 0 { 512 } else { 256 };
+
+            (8, height, count, 4, height as usize, mode & 0x06 != 0)
+        }
+        else if data.starts_with(&[0x72, 0xb5, 0x4a, 0x86]) {
+            let glyph_start = read_u32(8)? as usize;
+            let flags = read_u32(12)?;
+            let count = read_u32(16)? as usize;
+            let glyph_size = read_u32(20)? as usize;
+            let height = read_u32(24)?;
+            let width = read_u32(28)?;
+
+            (width, height, count, glyph_start, glyph_size, flags & 0x01 != 0)
+        }
+        else {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "Not a PSF font"));
+        };
+
+        let row_size = width.div_ceil(8) as usize;
+
+        if width == 0 || height == 0 || glyph_size < row_size * height as usize {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "Invalid PSF glyph size"));
+        }
+
+        let bitmaps = (0..count)
+            .map(|i| {
+                let start = glyph_start + i * glyph_size;
+                let glyph = data.get(start..start + glyph_size).ok_or_else(truncated)?;
+
+                Ok(decode_glyph(glyph, width, height, row_size))
+            })
+            .collect::<io::Result<Vec<Vec<bool>>>>()?;
+
+        let mut glyphs = HashMap::new();
+
+        if !table {
+            for (i, bitmap) in bitmaps.into_iter().enumerate() {
+                if let Some(character) = char::from_u32(i as u32) {
+                    glyphs.insert(character, bitmap);
+                }
+            }
+        }
+        else if data.starts_with(&[0x36, 0x04]) {
+            // each glyph lists its UCS-2 code points, ending with 0xFFFF; 0xFFFE starts combining sequences
+            let mut offset = glyph_start + count * glyph_size;
+
+            for bitmap in bitmaps {
+                let mut in_sequence = false;
+
+                loop {
+                    let bytes = data.get(offset..offset + 2).ok_or_else(truncated)?;
+                    let value = u16::from_le_bytes([bytes[0], bytes[1]]);
+                    offset += 2;
+
+                    match value {
+                        0xFFFF => break,
+                        0xFFFE => in_sequence = true,
+                        _ if in_sequence => {}
+                        _ => {
+                            if let Some(character) = char::from_u32(u32::from(value)) {
+                                glyphs.entry(character).or_insert_with(|| bitmap.clone());
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        else {
+            // each glyph lists its UTF-8 characters, ending with 0xFF; 0xFE starts combining sequences
+            let mut offset = glyph_start + count * glyph_size;
+
+            for bitmap in bitmaps {
+                let end = data[offset.min(data.len())..].iter().position(|&byte| byte == 0xFF).ok_or_else(truncated)?;
+                let entry = &data[offset..offset + end];
+                let singles = entry.split(|&byte| byte == 0xFE).next().unwrap_or(&[]);
+
+                let characters = std::str::from_utf8(singles)
+                    .map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "Invalid PSF Unicode table"))?;
+
+                for character in characters.chars() {
+                    glyphs.entry(character).or_insert_with(|| bitmap.clone());
+                }
+
+                offset += end + 1;
+            }
+        }
+
+        Ok(Font {
+            width,
+            height,
+            glyphs
+        })
+    }
+
+    /// Loads a Glyph Bitmap Distribution Format font from a file.
+    ///
+    /// Every glyph is placed in a cell the size of the font's bounding box, so proportional fonts are drawn with a
+    /// fixed advance. Fonts whose bounding box is larger than 1024 pixels in either direction are rejected.
+    ///
+    /// # Arguments
+    ///
+    /// * `file` - The .bdf file to read.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Font` struct, or an error if the file could not be read or is not a valid BDF font.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::fs::File;
+    /// use std::io;
+    /// use bumpy::bmp::text::Font;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut file = File::open("sample.bdf")?;
+    ///     let font = Font::build_from_bdf_file(&mut file)?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn build_from_bdf_file(file: &mut File) -> io::Result<Self> {
+        let mut source = String::new();
+        file.read_to_string(&mut source)?;
+
+        let invalid = |message: &str| io::Error::new(io::ErrorKind::InvalidData, format!("Invalid BDF font: {}", message));
+        let numbers = |values: &[&str]| {
+            values.iter()
+                .map(|value| value.parse::<i64>().map_err(|_| invalid("expected a number")))
+                .collect::<io::Result<Vec<i64>>>()
+        };
+
+        let mut bounds = None;
+        let mut glyphs = HashMap::new();
+        let mut lines = source.lines();
+
+        let mut encoding = None;
+        let mut glyph_bounds = None;
+
+        while let Some(line) = lines.next() {
+            let words: Vec<&str> = line.split_whitespace().collect();
+
+            match words.as_slice() {
+                ["FONTBOUNDINGBOX", values @ ..] if values.len() == 4 => {
+                    let values = numbers(values)?;
+
+                    if values[0] <= 0 || values[1] <= 0 {
+                        return Err(invalid("empty FONTBOUNDINGBOX"));
+                    }
+
+                    if values[0] > MAX_CELL_SIZE || values[1] > MAX_CELL_SIZE {
+                        return Err(invalid("FONTBOUNDINGBOX too large"));
+                    }
+
+                    bounds = Some(values);
+                }
+                ["STARTCHAR", ..] => {
+                    encoding = None;
+                    glyph_bounds = None;
+                }
+                ["ENCODING", value, ..] => encoding = Some(numbers(&[value])?[0]),
+                ["BBX", values @ ..] if values.len() == 4 => glyph_bounds = Some(numbers(values)?),
+                ["BITMAP"] => {
+                    let font_bounds = bounds.as_ref().ok_or_else(|| invalid("missing FONTBOUNDINGBOX"))?;
+                    let glyph_bounds = glyph_bounds.as_ref().unwrap_or(font_bounds);
+
+                    let (cell_width, cell_height) = (font_bounds[0], font_bounds[1]);
+                    let (glyph_width, glyph_height) = (glyph_bounds[0], glyph_bounds[1]);
+
+                    // offsets of the glyph's top-left corner within the cell, both measured from the baseline
+                    let left = glyph_bounds[2] - font_bounds[2];
+                    let top = (cell_height + font_bounds[3]) - (glyph_height + glyph_bounds[3]);
+
+                    let cell_size = (cell_width as usize).checked_mul(cell_height as usize)
+                        .ok_or_else(|| invalid("FONTBOUNDINGBOX too large"))?;
+                    let mut bitmap = vec![false; cell_size];
+
+                    for row in 0..glyph_height {
+                        let hex = lines.next().ok_or_else(|| invalid("truncated bitmap"))?.trim();
+
+                        if !hex.is_ascii() {
+                            return Err(invalid("bad bitmap row"));
+                        }
+
+                        let bytes = (0..hex.len() / 2)
+                            .map(|i| u8::from_str_radix(&hex[2 * i..2 * i + 2], 16).map_err(|_| invalid("bad bitmap row")))
+                            .collect::<io::Result<Vec<u8>>>()?;
+
+                        // columns past the end of the row are never set
+                        for column in 0..glyph_width.min(8 * bytes.len() as i64) {
+                            let set = bytes.get((column / 8) as usize).is_some_and(|byte| byte >> (7 - column % 8) & 1 == 1);
+                            let x = left + column;
+                            let y = top + row;
+
+                            if set && x >= 0 && y >= 0 && x < cell_width && y < cell_height {
+                                bitmap[(x + cell_width * y) as usize] = true;
+                            }
+                        }
+                    }
+
+                    if let Some(character) = encoding.and_then(|value| u32::try_from(value).ok()).and_then(char::from_u32) {
+                        glyphs.insert(character, bitmap);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        let bounds = bounds.ok_or_else(|| invalid("missing FONTBOUNDINGBOX"))?;
+
+        Ok(Font {
+            width: bounds[0] as u32,
+            height: bounds[1] as u32,
+            glyphs
+        })
+    }
+
+    /// Measures the area covered by a string drawn with this font.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - The string, with lines separated by `\n`.
+    /// * `scale` - The whole number of image pixels each font pixel is scaled up to.
+    ///
+    /// # Returns
+    ///
+    /// Returns the `(width, height)` of the text in pixels.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bumpy::bmp::text::Font;
+    ///
+    /// let font = Font::builtin();
+    ///
+    /// assert_eq!(font.measure("Hello\nworld!", 2), (96, 32));
+    /// ```
+    pub fn measure(&self, text: &str, scale: u32) -> (u32, u32) {
+        let columns = text.split('\n').map(|line| line.chars().count()).max().unwrap_or(0) as u32;
+        let rows = text.split('\n').count() as u32;
+
+        (columns * self.width * scale, rows * self.height * scale)
+    }
+
+    // the bitmap for a character, falling back to '?' for characters the font lacks
+    fn glyph(&self, character: char) -> Option<&Vec<bool>> {
+        self.glyphs.get(&character).or_else(|| self.glyphs.get(&'?'))
+    }
+}
+
+impl Default for Font {
+    fn default() -> Self {
+        Self::builtin()
+    }
+}
+
+impl Bmp {
+
+    /// Draws a string onto the image with its top-left corner at (`x`, `y`).
+    ///
+    /// Lines are separated by `\n`, and characters missing from the font are drawn as `?`. Text running off the
+    /// edges of the image is clipped.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - The string to draw.
+    /// * `x` - The column of the left edge of the text.
+    /// * `y` - The row, counted from the top, of the top edge of the text.
+    /// * `font` - The font to draw the text with.
+    /// * `style` - The colour, scale and background of the text.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::fs::File;
+    /// use std::io;
+    /// use bumpy::bmp::Bmp;
+    /// use bumpy::bmp::text::{Font, TextStyle};
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut file = File::open("sample.bmp")?;
+    ///     let mut bmp = Bmp::build_from_file(&mut file)?;
+    ///
+    ///     let style = TextStyle { colour: (255, 255, 0), scale: 3, background: Some((0, 0, 0)) };
+    ///     bmp.draw_text("Hello,\nworld!", 10, 10, &Font::builtin(), &style);
+    ///
+    ///     bmp.write_to_file("test")?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn draw_text(&mut self, text: &str, x: i32, y: i32, font: &Font, style: &TextStyle) {
+        if style.scale == 0 {
+            panic!("Text scale must be greater than zero");
+        }
+
+        let cell_width = font.width * style.scale;
+        let cell_height = font.height * style.scale;
+
+        // the position `count` steps of `size` pixels along from `start`, kept within range of the drawing functions
+        let offset = |start: i32, count: u32, size: u32| {
+            (i64::from(start) + i64::from(count) * i64::from(size)).clamp(i64::from(i32::MIN), i64::from(i32::MAX)) as i32
+        };
+
+        for (row, line) in text.split('\n').enumerate() {
+            let top = offset(y, row as u32, cell_height);
+
+            for (column, character) in line.chars().enumerate() {
+                let left = offset(x, column as u32, cell_width);
+
+                if let Some(colour) = style.background {
+                    self.fill_rect(left, top, cell_width, cell_height, colour);
+                }
+
+                let Some(glyph) = font.glyph(character) else {
+                    continue;
+                };
+
+                for (i, _) in glyph.iter().enumerate().filter(|(_, &set)| set) {
+                    let glyph_x = i as u32 % font.width;
+                    let glyph_y = i as u32 / font.width;
+
+                    self.fill_rect(
+                        offset(left, glyph_x, style.scale),
+                        offset(top, glyph_y, style.scale),
+                        style.scale,
+                        style.scale,
+                        style.colour
+                    );
+                }
+            }
+        }
+    }
+}
+
+// unpacks a glyph stored as rows of bytes, most significant bit leftmost
+fn decode_glyph(glyph: &[u8], width: u32, height: u32, row_size: usize) -> Vec<bool> {
+    (0..height)
+        .flat_map(|y| (0..width).map(move |x| glyph[y as usize * row_size + (x / 8) as usize] >> (7 - x % 8) & 1 == 1))
+        .collect()
+}
+
+// the largest cell width or height accepted from a BDF font
+const MAX_CELL_SIZE: i64 = 1024;
+
+// the embedded 8x8 font for the printable ASCII characters ' ' to '~', one byte per row
+const BUILTIN_GLYPHS: [[u8; 8]; 95] = [
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00], // space
+    [0x18, 0x3C, 0x3C, 0x18, 0x18, 0x00, 0x18, 0x00], // '!'
+    [0x36, 0x36, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00], // '"'
+    [0x36, 0x36, 0x7F, 0x36, 0x7F, 0x36, 0x36, 0x00], // '#'
+    [0x0C, 0x3E, 0x03, 0x1E, 0x30, 0x1F, 0x0C, 0x00], // '$'
+    [0x00, 0x63, 0x33, 0x18, 0x0C, 0x66, 0x63, 0x00], // '%'
+    [0x1C, 0x36, 0x1C, 0x6E, 0x3B, 0x33, 0x6E, 0x00], // '&'
+    [0x06, 0x06, 0x03, 0x00, 0x00, 0x00, 0x00, 0x00], // '\''
+    [0x18, 0x0C, 0x06, 0x06, 0x06, 0x0C, 0x18, 0x00], // '('
+    [0x06, 0x0C, 0x18, 0x18, 0x18, 0x0C, 0x06, 0x00], // ')'
+    [0x00, 0x66, 0x3C, 0xFF, 0x3C, 0x66, 0x00, 0x00], // '*'
+    [0x00, 0x0C, 0x0C, 0x3F, 0x0C, 0x0C, 0x00, 0x00], // '+'
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x0C, 0x0C, 0x06], // ','
+    [0x00, 0x00, 0x00, 0x3F, 0x00, 0x00, 0x00, 0x00], // '-'
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x0C, 0x0C, 0x00], // '.'
+    [0x60, 0x30, 0x18, 0x0C, 0x06, 0x03, 0x01, 0x00], // '/'
+    [0x3E, 0x63, 0x73, 0x7B, 0x6F, 0x67, 0x3E, 0x00], // '0'
+    [0x0C, 0x0E, 0x0C, 0x0C, 0x0C, 0x0C, 0x3F, 0x00], // '1'
+    [0x1E, 0x33, 0x30, 0x1C, 0x06, 0x33, 0x3F, 0x00], // '2'
+    [0x1E, 0x33, 0x30, 0x1C, 0x30, 0x33, 0x1E, 0x00], // '3'
+    [0x38, 0x3C, 0x36, 0x33, 0x7F, 0x30, 0x78, 0x00], // '4'
+    [0x3F, 0x03, 0x1F, 0x30, 0x30, 0x33, 0x1E, 0x00], // '5'
+    [0x1C, 0x06, 0x03, 0x1F, 0x33, 0x33, 0x1E, 0x00], // '6'
+    [0x3F, 0x33, 0x30, 0x18, 0x0C, 0x0C, 0x0C, 0x00], // '7'
+    [0x1E, 0x33, 0x33, 0x1E, 0x33, 0x33, 0x1E, 0x00], // '8'
+    [0x1E, 0x33, 0x33, 0x3E, 0x30, 0x18, 0x0E, 0x00], // '9'
+    [0x00, 0x0C, 0x0C, 0x00, 0x00, 0x0C, 0x0C, 0x00], // ':'
+    [0x00, 0x0C, 0x0C, 0x00, 0x00, 0x0C, 0x0C, 0x06], // ';'
+    [0x18, 0x0C, 0x06, 0x03, 0x06, 0x0C, 0x18, 0x00], // '<'
+    [0x00, 0x00, 0x3F, 0x00, 0x00, 0x3F, 0x00, 0x00], // '='
+    [0x06, 0x0C, 0x18, 0x30, 0x18, 0x0C, 0x06, 0x00], // '>'
+    [0x1E, 0x33, 0x30, 0x18, 0x0C, 0x00, 0x0C, 0x00], // '?'
+    [0x3E, 0x63, 0x7B, 0x7B, 0x7B, 0x03, 0x1E, 0x00], // '@'
+    [0x0C, 0x1E, 0x33, 0x33, 0x3F, 0x33, 0x33, 0x00], // 'A'
+    [0x3F, 0x66, 0x66, 0x3E, 0x66, 0x66, 0x3F, 0x00], // 'B'
+    [0x3C, 0x66, 0x03, 0x03, 0x03, 0x66, 0x3C, 0x00], // 'C'
+    [0x1F, 0x36, 0x66, 0x66, 0x66, 0x36, 0x1F, 0x00], // 'D'
+    [0x7F, 0x46, 0x16, 0x1E, 0x16, 0x46, 0x7F, 0x00], // 'E'
+    [0x7F, 0x46, 0x16, 0x1E, 0x16, 0x06, 0x0F, 0x00], // 'F'
+    [0x3C, 0x66, 0x03, 0x03, 0x73, 0x66, 0x7C, 0x00], // 'G'
+    [0x33, 0x33, 0x33, 0x3F, 0x33, 0x33, 0x33, 0x00], // 'H'
+    [0x1E, 0x0C, 0x0C, 0x0C, 0x0C, 0x0C, 0x1E, 0x00], // 'I'
+    [0x78, 0x30, 0x30, 0x30, 0x33, 0x33, 0x1E, 0x00], // 'J'
+    [0x67, 0x66, 0x36, 0x1E, 0x36, 0x66, 0x67, 0x00], // 'K'
+    [0x0F, 0x06, 0x06, 0x06, 0x46, 0x66, 0x7F, 0x00], // 'L'
+    [0x63, 0x77, 0x7F, 0x7F, 0x6B, 0x63, 0x63, 0x00], // 'M'
+    [0x63, 0x67, 0x6F, 0x7B, 0x73, 0x63, 0x63, 0x00], // 'N'
+    [0x1C, 0x36, 0x63, 0x63, 0x63, 0x36, 0x1C, 0x00], // 'O'
+    [0x3F, 0x66, 0x66, 0x3E, 0x06, 0x06, 0x0F, 0x00], // 'P'
+    [0x1E, 0x33, 0x33, 0x33, 0x3B, 0x1E, 0x38, 0x00], // 'Q'
+    [0x3F, 0x66, 0x66, 0x3E, 0x36, 0x66, 0x67, 0x00], // 'R'
+    [0x1E, 0x33, 0x07, 0x0E, 0x38, 0x33, 0x1E, 0x00], // 'S'
+    [0x3F, 0x2D, 0x0C, 0x0C, 0x0C, 0x0C, 0x1E, 0x00], // 'T'
+    [0x33, 0x33, 0x33, 0x33, 0x33, 0x33, 0x3F, 0x00], // 'U'
+    [0x33, 0x33, 0x33, 0x33, 0x33, 0x1E, 0x0C, 0x00], // 'V'
+    [0x63, 0x63, 0x63, 0x6B, 0x7F, 0x77, 0x63, 0x00], // 'W'
+    [0x63, 0x63, 0x36, 0x1C, 0x1C, 0x36, 0x63, 0x00], // 'X'
+    [0x33, 0x33, 0x33, 0x1E, 0x0C, 0x0C, 0x1E, 0x00], // 'Y'
+    [0x7F, 0x63, 0x31, 0x18, 0x4C, 0x66, 0x7F, 0x00], // 'Z'
+    [0x1E, 0x06, 0x06, 0x06, 0x06, 0x06, 0x1E, 0x00], // '['
+    [0x03, 0x06, 0x0C, 0x18, 0x30, 0x60, 0x40, 0x00], // '\\'
+    [0x1E, 0x18, 0x18, 0x18, 0x18, 0x18, 0x1E, 0x00], // ']'
+    [0x08, 0x1C, 0x36, 0x63, 0x00, 0x00, 0x00, 0x00], // '^'
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xFF], // '_'
+    [0x0C, 0x0C, 0x18, 0x00, 0x00, 0x00, 0x00, 0x00], // '`'
+    [0x00, 0x00, 0x1E, 0x30, 0x3E, 0x33, 0x6E, 0x00], // 'a'
+    [0x07, 0x06, 0x06, 0x3E, 0x66, 0x66, 0x3B, 0x00], // 'b'
+    [0x00, 0x00, 0x1E, 0x33, 0x03, 0x33, 0x1E, 0x00], // 'c'
+    [0x38, 0x30, 0x30, 0x3E, 0x33, 0x33, 0x6E, 0x00], // 'd'
+    [0x00, 0x00, 0x1E, 0x33, 0x3F, 0x03, 0x1E, 0x00], // 'e'
+    [0x1C, 0x36, 0x06, 0x0F, 0x06, 0x06, 0x0F, 0x00], // 'f'
+    [0x00, 0x00, 0x6E, 0x33, 0x33, 0x3E, 0x30, 0x1F], // 'g'
+    [0x07, 0x06, 0x36, 0x6E, 0x66, 0x66, 0x67, 0x00], // 'h'
+    [0x0C, 0x00, 0x0E, 0x0C, 0x0C, 0x0C, 0x1E, 0x00], // 'i'
+    [0x30, 0x00, 0x30, 0x30, 0x30, 0x33, 0x33, 0x1E], // 'j'
+    [0x07, 0x06, 0x66, 0x36, 0x1E, 0x36, 0x67, 0x00], // 'k'
+    [0x0E, 0x0C, 0x0C, 0x0C, 0x0C, 0x0C, 0x1E, 0x00], // 'l'
+    [0x00, 0x00, 0x33, 0x7F, 0x7F, 0x6B, 0x63, 0x00], // 'm'
+    [0x00, 0x00, 0x1F, 0x33, 0x33, 0x33, 0x33, 0x00], // 'n'
+    [0x00, 0x00, 0x1E, 0x33, 0x33, 0x33, 0x1E, 0x00], // 'o'
+    [0x00, 0x00, 0x3B, 0x66, 0x66, 0x3E, 0x06, 0x0F], // 'p'
+    [0x00, 0x00, 0x6E, 0x33, 0x33, 0x3E, 0x30, 0x78], // 'q'
+    [0x00, 0x00, 0x3B, 0x6E, 0x66, 0x06, 0x0F, 0x00], // 'r'
+    [0x00, 0x00, 0x3E, 0x03, 0x1E, 0x30, 0x1F, 0x00], // 's'
+    [0x08, 0x0C, 0x3E, 0x0C, 0x0C, 0x2C, 0x18, 0x00], // 't'
+    [0x00, 0x00, 0x33, 0x33, 0x33, 0x33, 0x6E, 0x00], // 'u'
+    [0x00, 0x00, 0x33, 0x33, 0x33, 0x1E, 0x0C, 0x00], // 'v'
+    [0x00, 0x00, 0x63, 0x6B, 0x7F, 0x7F, 0x36, 0x00], // 'w'
+    [0x00, 0x00, 0x63, 0x36, 0x1C, 0x36, 0x63, 0x00], // 'x'
+    [0x00, 0x00, 0x33, 0x33, 0x33, 0x3E, 0x30, 0x1F], // 'y'
+    [0x00, 0x00, 0x3F, 0x19, 0x0C, 0x26, 0x3F, 0x00], // 'z'
+    [0x38, 0x0C, 0x0C, 0x07, 0x0C, 0x0C, 0x38, 0x00], // '{'
+    [0x18, 0x18, 0x18, 0x00, 0x18, 0x18, 0x18, 0x00], // '|'
+    [0x07, 0x0C, 0x0C, 0x38, 0x0C, 0x0C, 0x07, 0x00], // '}'
+    [0x6E, 0x3B, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00], // '~'
+];
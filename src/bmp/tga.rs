@@ -0,0 +1,383 @@
+use std::fs::File;
+use std::io::{self, BufWriter, Read, Write};
+use crate::bmp::Bmp;
+use crate::bmp::bmp_colour_table::BmpColourTable;
+use crate::bmp::colour::greyscale_image;
+
+// footer identifying a TGA 2.0 file, written after empty extension and developer area offsets
+const TGA_SIGNATURE: &[u8; 18] = b"TRUEVISION-XFILE.\0";
+
+/// Whether `write_to_tga_file` compresses the pixel data.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TgaCompression {
+    /// Stores every pixel as is.
+    None,
+    /// Run-length encodes each row, shrinking images with large areas of flat colour.
+    Rle,
+}
+
+/// The corner of the image `write_to_tga_file` stores first.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TgaOrigin {
+    /// Rows are stored from the bottom up, like a .bmp file.
+    BottomLeft,
+    /// Rows are stored from the top down.
+    TopLeft,
+}
+
+impl Bmp {
+
+    /// Builds a `Bmp` struct from a TGA file.
+    ///
+    /// Uncompressed and run-length encoded images are supported. Colour-mapped images become 8-bit images, greyscale
+    /// images become 8-bit greyscale images, and 24-bit and 32-bit true-colour images keep their bit depth. The origin
+    /// flags are honoured, so images stored from any corner come out the right way up. The fourth byte of 32-bit pixels
+    /// is only read as alpha when the header declares alpha bits, otherwise the image is fully opaque.
+    ///
+    /// # Arguments
+    ///
+    /// * `file` - The file to read from.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` containing the `Bmp` if successful, or an `io::Error` if an error occurred.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::fs::File;
+    /// use std::io;
+    /// use bumpy::bmp::Bmp;
+    /// use bumpy::bmp::tga::{TgaCompression, TgaOrigin};
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut file = File::open("sample.bmp")?;
+    ///     let bmp = Bmp::build_from_file(&mut file)?;
+    ///
+    ///     bmp.write_to_tga_file("test_tga_rle", TgaCompression::Rle, TgaOrigin::TopLeft)?;
+    ///
+    ///     let mut file = File::open("test_tga_rle.tga")?;
+    ///     let loaded = Bmp::build_from_tga_file(&mut file)?;
+    ///     std::fs::remove_file("test_tga_rle.tga")?;
+    ///
+    ///     assert_eq!(loaded, bmp);
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn build_from_tga_file(file: &mut File) -> io::Result<Self> {
+        let mut bytes = Vec::new();
+        file.read_to_end(&mut bytes)?;
+
+        let invalid = |message: &str| io::Error::new(io::ErrorKind::InvalidData, format!("Invalid TGA image: {}", message));
+        let header = bytes.get(..18).ok_or_else(|| invalid("truncated header"))?;
+
+        let id_length = usize::from(header[0]);
+        let colour_map_type = header[1];
+        let image_type = header[2];
+        let first_entry = usize::from(u16::from_le_bytes([header[3], header[4]]));
+        let map_length = usize::from(u16::from_le_bytes([header[5], header[6]]));
+        let entry_size = header[7];
+        let width = u32::from(u16::from_le_bytes([header[12], header[13]]));
+        let height = u32::from(u16::from_le_bytes([header[14], header[15]]));
+        let pixel_depth = header[16];
+        let descriptor = header[17];
+
+        if width == 0 || height == 0 {
+            return Err(invalid("image width and height must be greater than zero"));
+        }
+
+        let supported = match image_type & !8 {
+            1 => pixel_depth == 8 && colour_map_type == 1,
+            2 => pixel_depth == 24 || pixel_depth == 32,
+            3 => pixel_depth == 8,
+            _ => false,
+        };
+
+        if !supported {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "Only 32-bit, 24-bit or 8-bit .tga files are supported"));
+        }
+
+        let mut position = 18 + id_length;
+
+        // the colour map is present even for true-colour images that carry one, so it always has to be skipped
+        let mut palette = Vec::new();
+
+        if colour_map_type == 1 {
+            let entry_bytes = usize::from(entry_size).div_ceil(8);
+            let map = bytes.get(position..position + map_length * entry_bytes).ok_or_else(|| invalid("truncated colour map"))?;
+            position += map.len();
+
+            if image_type & !8 == 1 {
+                if first_entry + map_length > 256 {
+                    return Err(invalid("colour map has more than 256 entries"));
+                }
+
+                palette = vec![(0, 0, 0, 0); first_entry];
+
+                for entry in map.chunks_exact(entry_bytes) {
+                    palette.push(match entry_bytes {
+                        2 => {
+                            // five bits per channel, red highest
+                            let value = u16::from_le_bytes([entry[0], entry[1]]);
+                            let expand = |bits: u16| ((bits & 31) * 255 / 31) as u8;
+
+                            (expand(value), expand(value >> 5), expand(value >> 10), 0)
+                        }
+                        3 | 4 => (entry[0], entry[1], entry[2], 0),
+                        _ => return Err(invalid("unsupported colour map entry size")),
+                    });
+                }
+            }
+        }
+
+        let bytes_per_px = usize::from(pixel_depth / 8);
+        let count = (width * height) as usize;
+        let data = &bytes[position.min(bytes.len())..];
+
+        let stored = if image_type & 8 != 0 {
+            decode_rle(data, bytes_per_px, count).ok_or_else(|| invalid("truncated run-length data"))?
+        }
+        else {
+            data.get(..count * bytes_per_px).ok_or_else(|| invalid("truncated pixel data"))?.to_vec()
+        };
+
+        // put the pixels in top-down, left-to-right order, whichever corner the file starts from
+        let right_to_left = descriptor & 0x10 != 0;
+        let top_to_bottom = descriptor & 0x20 != 0;
+        let mut ordered = vec![0; stored.len()];
+
+        for (i, pixel) in stored.chunks_exact(bytes_per_px).enumerate() {
+            let column = i as u32 % width;
+            let row = i as u32 / width;
+
+            let x = if right_to_left { width - 1 - column } else { column };
+            let y = if top_to_bottom { row } else { height - 1 - row };
+            let offset = (x + width * y) as usize * bytes_per_px;
+
+            ordered[offset..offset + bytes_per_px].copy_from_slice(pixel);
+        }
+
+        let bmp = match image_type & !8 {
+            1 => {
+                let mut bmp = Bmp::builder().size(width, height).depth(8).palette(BmpColourTable { data: palette }).build();
+                bmp.set_indices(width, height, 8, &ordered);
+                bmp
+            }
+            3 => greyscale_image(width, height, &ordered),
+            _ => {
+                // the fourth byte is only alpha when the descriptor says it holds alpha bits
+                let has_alpha = descriptor & 0x0F != 0;
+
+                let pixels: Vec<(u8, u8, u8, u8)> = ordered.chunks_exact(bytes_per_px)
+                    .map(|pixel| (pixel[0], pixel[1], pixel[2], pixel.get(3).filter(|_| has_alpha).copied().unwrap_or(255)))
+                    .collect();
+
+                let bits_per_px = u16::from(pixel_depth);
+                let mut bmp = Bmp::builder().size(width, height).depth(bits_per_px).build();
+                bmp.set_pixels(width, height, bits_per_px, &pixels);
+                bmp
+            }
+        };
+
+        Ok(bmp)
+    }
+
+    /// Writes the image to a TGA file.
+    ///
+    /// 24-bit and 32-bit images are stored as true-colour images, keeping the alpha channel of 32-bit images that have
+    /// one. 1-bit and 8-bit images are stored as 8-bit colour-mapped images.
+    ///
+    /// # Arguments
+    ///
+    /// * `file_name` - The name of the file to write to, without the `.tga` extension.
+    /// * `compression` - Whether to run-length encode the pixel data.
+    /// * `origin` - The corner of the image stored first.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` containing `()` if successful, or an `io::Error` if an error occurred.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::fs::File;
+    /// use std::io;
+    /// use bumpy::bmp::Bmp;
+    /// use bumpy::bmp::tga::{TgaCompression, TgaOrigin};
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut file = File::open("sample2.bmp")?;
+    ///     let bmp = Bmp::build_from_file(&mut file)?;
+    ///
+    ///     bmp.write_to_tga_file("test_tga_uncompressed", TgaCompression::None, TgaOrigin::BottomLeft)?;
+    ///
+    ///     let mut file = File::open("test_tga_uncompressed.tga")?;
+    ///     let loaded = Bmp::build_from_tga_file(&mut file)?;
+    ///     std::fs::remove_file("test_tga_uncompressed.tga")?;
+    ///
+    ///     assert_eq!(loaded, bmp);
+    ///     assert_eq!(loaded.colour_table, bmp.colour_table);
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn write_to_tga_file(&self, file_name: &str, compression: TgaCompression, origin: TgaOrigin) -> io::Result<()> {
+        let bits_per_px = self.bits_per_px();
+        let width = self.width();
+        let height = self.height();
+
+        self.check_supported_depth();
+
+        if width > u32::from(u16::MAX) || height > u32::from(u16::MAX) {
+            panic!("TGA images can be at most 65535 pixels wide and high");
+        }
+
+        let indexed = bits_per_px <= 8;
+
+        // indexed images without a colour table are grey, so write out the colours they are shown with
+        let palette: Vec<(u8, u8, u8, u8)> = if !indexed {
+            Vec::new()
+        }
+        else if self.colour_table.is_empty() {
+            (0..1u16 << bits_per_px).map(|index| self.palette_colour(index as u8)).collect()
+        }
+        else {
+            self.colour_table.data.clone()
+        };
+
+        let (bytes_per_px, rows): (usize, Vec<Vec<u8>>) = if indexed {
+            (1, self.indices().chunks(width as usize).map(<[u8]>::to_vec).collect())
+        }
+        else {
+            let bytes_per_px = usize::from(bits_per_px / 8);
+            let rows = self.pixels()
+                .chunks(width as usize)
+                .map(|row| row.iter().flat_map(|&(b, g, r, a)| [b, g, r, a].into_iter().take(bytes_per_px)).collect())
+                .collect();
+
+            (bytes_per_px, rows)
+        };
+
+        let image_type = match (indexed, compression) {
+            (true, TgaCompression::None) => 1,
+            (false, TgaCompression::None) => 2,
+            (true, TgaCompression::Rle) => 9,
+            (false, TgaCompression::Rle) => 10,
+        };
+
+        // 32-bit images without an alpha channel are written fully opaque, declaring no alpha bits
+        let alpha_bits = if bits_per_px == 32 && self.has_alpha() { 8 } else { 0 };
+        let descriptor = alpha_bits | if origin == TgaOrigin::TopLeft { 0x20 } else { 0 };
+        let pixel_depth = if indexed { 8 } else { bits_per_px as u8 };
+
+        let mut header = [0; 18];
+        header[1] = u8::from(indexed);
+        header[2] = image_type;
+        header[5..7].copy_from_slice(&(palette.len() as u16).to_le_bytes());
+        header[7] = if indexed { 24 } else { 0 };
+        header[12..14].copy_from_slice(&(width as u16).to_le_bytes());
+        header[14..16].copy_from_slice(&(height as u16).to_le_bytes());
+        header[16] = pixel_depth;
+        header[17] = descriptor;
+
+        let mut file = BufWriter::new(File::create(format!("{}.tga", file_name))?);
+
+        file.write_all(&header)?;
+
+        for &(b, g, r, _) in &palette {
+            file.write_all(&[b, g, r])?;
+        }
+
+        let ordered: Box<dyn Iterator<Item = &Vec<u8>>> = match origin {
+            TgaOrigin::BottomLeft => Box::new(rows.iter().rev()),
+            TgaOrigin::TopLeft => Box::new(rows.iter()),
+        };
+
+        for row in ordered {
+            match compression {
+                TgaCompression::None => file.write_all(row)?,
+                TgaCompression::Rle => file.write_all(&encode_rle(row, bytes_per_px))?,
+            }
+        }
+
+        file.write_all(&[0; 8])?;
+        file.write_all(TGA_SIGNATURE)?;
+
+        file.flush()
+    }
+}
+
+// expands run-length packets until `count` pixels have been decoded
+fn decode_rle(data: &[u8], bytes_per_px: usize, count: usize) -> Option<Vec<u8>> {
+    // every packet takes at least a header byte and one pixel and holds at most 128 pixels, so a header claiming more
+    // than that is rejected before anything is allocated for it
+    if count > data.len() / (bytes_per_px + 1) * 128 {
+        return None;
+    }
+
+    let mut pixels = Vec::with_capacity(count * bytes_per_px);
+    let mut position = 0;
+
+    while pixels.len() < count * bytes_per_px {
+        let packet = *data.get(position)?;
+        let length = usize::from(packet & 0x7F) + 1;
+        position += 1;
+
+        if packet & 0x80 != 0 {
+            // a run packet repeats one pixel
+            let pixel = data.get(position..position + bytes_per_px)?;
+            position += bytes_per_px;
+
+            for _ in 0..length {
+                pixels.extend_from_slice(pixel);
+            }
+        }
+        else {
+            // a raw packet holds its pixels as is
+            pixels.extend_from_slice(data.get(position..position + length * bytes_per_px)?);
+            position += length * bytes_per_px;
+        }
+    }
+
+    pixels.truncate(count * bytes_per_px);
+
+    Some(pixels)
+}
+
+// encodes one row as run-length packets, using runs for two or more repeated pixels and raw packets for the rest
+fn encode_rle(row: &[u8], bytes_per_px: usize) -> Vec<u8> {
+    let pixels: Vec<&[u8]> = row.chunks_exact(bytes_per_px).collect();
+    let mut encoded = Vec::new();
+    let mut i = 0;
+
+    // the number of identical pixels starting at `start`, up to the longest a packet can hold
+    let run_length = |start: usize| {
+        pixels[start..].iter().take(128).take_while(|&&pixel| pixel == pixels[start]).count()
+    };
+
+    while i < pixels.len() {
+        let run = run_length(i);
+
+        if run >= 2 {
+            encoded.push(0x80 | (run - 1) as u8);
+            encoded.extend_from_slice(pixels[i]);
+            i += run;
+        }
+        else {
+            let start = i;
+
+            while i < pixels.len() && i - start < 128 && (i == start || run_length(i) < 2) {
+                i += 1;
+            }
+
+            encoded.push((i - start - 1) as u8);
+
+            for pixel in &pixels[start..i] {
+                encoded.extend_from_slice(pixel);
+            }
+        }
+    }
+
+    encoded
+}
@@ -0,0 +1,166 @@
+use crate::bmp::Bmp;
+use crate::bmp::convolve::gaussian_weights;
+use crate::bmp::pad::EdgeMode;
+use crate::bmp::utils::rgb_to_greyscale;
+
+/// How the cut-off between black and white pixels is chosen.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Threshold {
+    /// Pixels with a luminance above the given value become white.
+    Fixed(u8),
+    /// Picks the global threshold that best separates the luminance histogram into two classes.
+    Otsu,
+    /// Compares each pixel to the mean luminance of the square of the given radius around it, minus `offset`.
+    AdaptiveMean { radius: u32, offset: i32 },
+    /// Compares each pixel to the Gaussian weighted mean luminance around it, minus `offset`.
+    AdaptiveGaussian { sigma: f64, offset: i32 },
+}
+
+impl Bmp {
+
+    /// Converts the image to a 1-bit black and white image.
+    ///
+    /// Pixels are compared by luminance. The result has a two entry colour table, with black at index 0 and white at
+    /// index 1.
+    ///
+    /// # Arguments
+    ///
+    /// * `method` - How the threshold between black and white is chosen.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::fs::File;
+    /// use std::io;
+    /// use bumpy::bmp::Bmp;
+    /// use bumpy::bmp::threshold::Threshold;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut file = File::open("sample.bmp")?;
+    ///     let mut bmp = Bmp::build_from_file(&mut file)?;
+    ///
+    ///     bmp.threshold(Threshold::AdaptiveMean { radius: 7, offset: 5 });
+    ///
+    ///     bmp.write_to_file("test")?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn threshold(&mut self, method: Threshold) {
+        self.check_supported_depth();
+
+        let width = self.width();
+        let height = self.height();
+
+        let luminance: Vec<u8> = self.pixels()
+            .into_iter()
+            .map(|(b, g, r, _)| rgb_to_greyscale((b, g, r)).0)
+            .collect();
+
+        let white: Vec<bool> = match method {
+            Threshold::Fixed(level) => luminance.iter().map(|&value| value > level).collect(),
+            Threshold::Otsu => {
+                let level = otsu_level(&self.histogram().luminance);
+                luminance.iter().map(|&value| value > level).collect()
+            }
+            Threshold::AdaptiveMean { radius, offset } => {
+                let means = local_means(&luminance, width, height, radius);
+                compare_local(&luminance, &means, offset)
+            }
+            Threshold::AdaptiveGaussian { sigma, offset } => {
+                let channels: Vec<[f64; 3]> = luminance.iter().map(|&value| [f64::from(value); 3]).collect();
+                let means: Vec<f64> = self.convolve_separable(&channels, &gaussian_weights(sigma), EdgeMode::Replicate)
+                    .into_iter()
+                    .map(|[value, _, _]| value)
+                    .collect();
+
+                compare_local(&luminance, &means, offset)
+            }
+        };
+
+        let indices: Vec<u8> = white.into_iter().map(u8::from).collect();
+
+        self.colour_table.data = vec![(0, 0, 0, 0), (255, 255, 255, 0)];
+        self.set_indices(width, height, 1, &indices);
+    }
+}
+
+// marks pixels brighter than their local mean minus `offset`
+fn compare_local(luminance: &[u8], means: &[f64], offset: i32) -> Vec<bool> {
+    luminance.iter()
+        .zip(means.iter())
+        .map(|(&value, &mean)| f64::from(value) > mean - f64::from(offset))
+        .collect()
+}
+
+// mean luminance of the square around every pixel, clipped to the image, using a summed area table
+fn local_means(luminance: &[u8], width: u32, height: u32, radius: u32) -> Vec<f64> {
+    let width = width as usize;
+    let height = height as usize;
+    let radius = radius as usize;
+
+    // sums[(x + 1) + (width + 1) * (y + 1)] holds the sum of every value above and to the left of (x, y) inclusive
+    let mut sums = vec![0u64; (width + 1) * (height + 1)];
+
+    for y in 0..height {
+        let mut row_sum = 0;
+
+        for x in 0..width {
+            row_sum += u64::from(luminance[x + width * y]);
+            sums[(x + 1) + (width + 1) * (y + 1)] = sums[(x + 1) + (width + 1) * y] + row_sum;
+        }
+    }
+
+    let mut means = Vec::with_capacity(width * height);
+
+    for y in 0..height {
+        let top = y.saturating_sub(radius);
+        let bottom = (y + radius + 1).min(height);
+
+        for x in 0..width {
+            let left = x.saturating_sub(radius);
+            let right = (x + radius + 1).min(width);
+
+            let sum = sums[right + (width + 1) * bottom] + sums[left + (width + 1) * top]
+                - sums[left + (width + 1) * bottom] - sums[right + (width + 1) * top];
+            let area = (right - left) * (bottom - top);
+
+            means.push(sum as f64 / area as f64);
+        }
+    }
+
+    means
+}
+
+// finds the luminance that maximises the variance between the darker and lighter classes of pixels
+fn otsu_level(histogram: &[u32; 256]) -> u8 {
+    let total: f64 = histogram.iter().map(|&count| f64::from(count)).sum();
+    let total_sum: f64 = histogram.iter().enumerate().map(|(value, &count)| value as f64 * f64::from(count)).sum();
+
+    let mut dark_count = 0.0;
+    let mut dark_sum = 0.0;
+    let mut best_level = 0;
+    let mut best_variance = -1.0;
+
+    for (value, &count) in histogram.iter().enumerate() {
+        dark_count += f64::from(count);
+        dark_sum += value as f64 * f64::from(count);
+
+        let light_count = total - dark_count;
+
+        if dark_count == 0.0 || light_count == 0.0 {
+            continue;
+        }
+
+        let dark_mean = dark_sum / dark_count;
+        let light_mean = (total_sum - dark_sum) / light_count;
+        let variance = dark_count * light_count * (dark_mean - light_mean).powi(2);
+
+        if variance > best_variance {
+            best_variance = variance;
+            best_level = value as u8;
+        }
+    }
+
+    best_level
+}
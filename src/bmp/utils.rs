@@ -6,7 +6,17 @@ pub fn rgb_to_greyscale(bgr: (u8, u8, u8)) -> (u8, u8, u8) {
     (grey_value, grey_value, grey_value)
 }
 
+// rounds and clamps a channel value into the range of a byte
+pub(crate) fn clamp_channel(value: f64) -> u8 {
+    value.round().clamp(0.0, 255.0) as u8
+}
+
 
 pub fn round_up_to_multiple_of_four(value: u32) -> u32 {
-    ((value + 3) / 4) * 4
-}
\ No newline at end of file
+    value.div_ceil(4) * 4
+}
+
+// size in bytes of a padded row of pixels, done in u64 so huge header widths can't overflow
+pub(crate) fn padded_row_size(width: u32, bits_per_px: u32) -> u64 {
+    (u64::from(width) * u64::from(bits_per_px)).div_ceil(8).div_ceil(4) * 4
+}